@@ -1,10 +1,9 @@
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 use yatyat::{
     element::transformation::Transformation,
     semigroup::{
-        algs::froidure_pin::FroidurePinBuilder,
-        algs::froidure_pin::{froidure_pin_impl::FroidurePin, simple::FroidurePinSimple},
+        algs::froidure_pin::simple::FroidurePinSimple, algs::froidure_pin::FroidurePinBuilder,
         impls::transformation::TransformationSemigroup,
     },
 };
@@ -21,5 +20,5 @@ fn main() {
     let start = Instant::now();
     let res = fp.build();
     let end = start.elapsed();
-    println!("End, time={}ms", end.as_millis());
+    println!("End, time={}ms, size={}", end.as_millis(), res.size());
 }
@@ -0,0 +1,13 @@
+use snafu::Snafu;
+#[derive(Debug, Snafu)]
+pub enum BipartitionError {
+    #[snafu(display("Invalid blocks {:?} for degree {}", blocks, degree))]
+    InvalidBlocks {
+        degree: usize,
+        blocks: Vec<Vec<usize>>,
+    },
+    #[snafu(display("Invalid block lookup {:?} for degree {}", lookup, degree))]
+    InvalidLookup { degree: usize, lookup: Vec<usize> },
+    #[snafu(display("Operation only defined for equal degree : {} != {}", degree1, degree2))]
+    MismatchingDegree { degree1: usize, degree2: usize },
+}
@@ -0,0 +1,273 @@
+use std::sync::Arc;
+
+use super::SemigroupElement;
+use crate::utils::union_find::UnionFind;
+
+pub mod err;
+
+/// Representation of a bipartition of degree n, which is a partition of the points 0..n-1 and 0'..(n-1)'.
+/// The top points 0..n-1 are stored as 0..n-1, and the bottom points 0'..(n-1)' are stored as n..2n-1.
+/// This is stored as a vector giving the index of the block containing each point.
+/// Blocks are numbered in order of their smallest point, so equal bipartitions have equal representations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bipartition {
+    degree: usize,
+    lookup: Arc<[usize]>,
+}
+
+impl Bipartition {
+    pub fn as_vec(&self) -> &[usize] {
+        &self.lookup[..]
+    }
+
+    /// Create a bipartition from a list of blocks.
+    /// The top points are 0..n-1 and the bottom points are n..2n-1, and every point must appear in exactly one block.
+    /// ```
+    /// use yatyat::element::bipartition::Bipartition;
+    ///
+    /// // The blocks {0, 1'} and {1, 0'}
+    /// let f = Bipartition::from_blocks(2, &[vec![0, 3], vec![1, 2]]).unwrap();
+    /// assert_eq!(f.rank(), 2);
+    /// assert!(Bipartition::from_blocks(2, &[vec![0, 3], vec![1]]).is_err());
+    /// ```
+    pub fn from_blocks(
+        degree: usize,
+        blocks: &[Vec<usize>],
+    ) -> Result<Self, err::BipartitionError> {
+        let mut lookup = vec![None; 2 * degree];
+        for (index, block) in blocks.iter().enumerate() {
+            for &x in block {
+                match lookup.get_mut(x) {
+                    Some(val @ None) => *val = Some(index),
+                    // Point is either out of range or already in a block
+                    _ => {
+                        return Err(err::BipartitionError::InvalidBlocks {
+                            degree,
+                            blocks: blocks.to_vec(),
+                        })
+                    }
+                }
+            }
+        }
+        // Every point must be in a block
+        match lookup.into_iter().collect::<Option<Vec<_>>>() {
+            Some(lookup) => Ok(Self::from_vec_unchecked(degree, lookup)),
+            None => Err(err::BipartitionError::InvalidBlocks {
+                degree,
+                blocks: blocks.to_vec(),
+            }),
+        }
+    }
+
+    /// Create a bipartition from the index of the block containing each of the 2n points.
+    /// The block indices do not need to be normalised.
+    pub fn from_vec(degree: usize, lookup: Vec<usize>) -> Result<Self, err::BipartitionError> {
+        if lookup.len() != 2 * degree {
+            Err(err::BipartitionError::InvalidLookup { degree, lookup })
+        } else {
+            Ok(Self::from_vec_unchecked(degree, lookup))
+        }
+    }
+
+    /// Create a bipartition given a block lookup of the correct length, renumbering the blocks in order of first appearance.
+    pub(crate) fn from_vec_unchecked(degree: usize, lookup: Vec<usize>) -> Self {
+        let mut renumber = crate::DetHashMap::default();
+        let lookup: Vec<usize> = lookup
+            .into_iter()
+            .map(|block| {
+                let next = renumber.len();
+                *renumber.entry(block).or_insert(next)
+            })
+            .collect();
+        Self {
+            degree,
+            lookup: lookup.into(),
+        }
+    }
+
+    /// Return the identity bipartition on degree points, which has blocks {i, i'}
+    /// ```
+    /// use yatyat::element::bipartition::Bipartition;
+    ///
+    /// let id = Bipartition::id(5);
+    /// assert!(id.is_id())
+    /// ```
+    pub fn id(degree: usize) -> Self {
+        Self::from_vec_unchecked(degree, (0..degree).chain(0..degree).collect())
+    }
+
+    /// Return if a bipartition is the identity bipartition.
+    pub fn is_id(&self) -> bool {
+        (0..self.degree).all(|i| self.lookup[i] == i && self.lookup[i + self.degree] == i)
+    }
+
+    /// Return the degree of the bipartition
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Return the number of blocks in the bipartition.
+    pub fn number_of_blocks(&self) -> usize {
+        self.lookup.iter().max().map_or(0, |&x| x + 1)
+    }
+
+    /// Return the blocks of the bipartition, with the bottom points numbered n..2n-1.
+    pub fn blocks(&self) -> Vec<Vec<usize>> {
+        let mut blocks = vec![Vec::new(); self.number_of_blocks()];
+        for (x, &block) in self.lookup.iter().enumerate() {
+            blocks[block].push(x);
+        }
+        blocks
+    }
+
+    /// Return for each block if it is transverse, that is if it contains both top and bottom points.
+    pub fn transverse_blocks(&self) -> Vec<bool> {
+        let mut top = vec![false; self.number_of_blocks()];
+        let mut transverse = vec![false; self.number_of_blocks()];
+        for &block in &self.lookup[..self.degree] {
+            top[block] = true;
+        }
+        for &block in &self.lookup[self.degree..] {
+            transverse[block] = top[block];
+        }
+        transverse
+    }
+
+    /// Return the rank, which is the number of transverse blocks.
+    /// ```
+    /// use yatyat::element::bipartition::Bipartition;
+    ///
+    /// // The blocks {0, 1, 0'} and {1'}
+    /// let f = Bipartition::from_blocks(2, &[vec![0, 1, 2], vec![3]]).unwrap();
+    /// assert_eq!(f.rank(), 1);
+    /// ```
+    pub fn rank(&self) -> usize {
+        self.transverse_blocks().into_iter().filter(|&x| x).count()
+    }
+
+    /// Multiply two bipartitions by stacking the diagram of self on top of other.
+    /// The bottom points of self are glued to the top points of other, and any components that lie entirely in the middle are removed.
+    /// This is only defined for bipartitions of the same degree
+    /// ```
+    /// use yatyat::element::bipartition::Bipartition;
+    ///
+    /// // The blocks {0, 1}, {0', 1'}
+    /// let e = Bipartition::from_blocks(2, &[vec![0, 1], vec![2, 3]]).unwrap();
+    /// assert_eq!(e.multiply(&e).unwrap(), e);
+    /// ```
+    pub fn multiply(&self, other: &Self) -> Result<Self, err::BipartitionError> {
+        if self.degree != other.degree {
+            return Err(err::BipartitionError::MismatchingDegree {
+                degree1: self.degree,
+                degree2: other.degree,
+            });
+        }
+        let n = self.degree;
+        // Points 0..n are the top of self, n..2n are the middle, and 2n..3n are the bottom of other.
+        // The blocks are given points 3n.. in the forest, self first and then other.
+        let offset = 3 * n;
+        let other_offset = offset + self.number_of_blocks();
+        let mut forest = UnionFind::new(other_offset + other.number_of_blocks());
+        for (x, &block) in self.lookup.iter().enumerate() {
+            forest.union(x, offset + block);
+        }
+        for (x, &block) in other.lookup.iter().enumerate() {
+            forest.union(x + n, other_offset + block);
+        }
+        // The product is then the components restricted to the top and bottom.
+        let lookup = (0..n).chain(2 * n..3 * n).map(|x| forest.find(x)).collect();
+        Ok(Self::from_vec_unchecked(n, lookup))
+    }
+}
+
+impl SemigroupElement for Bipartition {
+    fn multiply(&self, other: &Self) -> Self {
+        // Will panic if degrees do not match
+        self.multiply(other).unwrap()
+    }
+
+    fn is_id(&self) -> bool {
+        self.is_id()
+    }
+}
+
+/// Display the blocks, writing bottom points as i'.
+impl std::fmt::Display for Bipartition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        let mut sep = "";
+        for block in self.blocks() {
+            write!(f, "{}{{", sep)?;
+            let mut point_sep = "";
+            for x in block {
+                if x < self.degree {
+                    write!(f, "{}{}", point_sep, x)?;
+                } else {
+                    write!(f, "{}{}'", point_sep, x - self.degree)?;
+                }
+                point_sep = ", ";
+            }
+            write!(f, "}}")?;
+            sep = ", ";
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Bipartition;
+
+    #[test]
+    fn id() {
+        let id0 = Bipartition::id(0);
+        let id10 = Bipartition::id(10);
+        let f = Bipartition::from_blocks(2, &[vec![0, 1, 2, 3]]).unwrap();
+        assert!(id0.is_id());
+        assert!(id10.is_id());
+        assert!(!f.is_id());
+        assert_eq!(id10.rank(), 10);
+    }
+
+    #[test]
+    fn invalid_blocks() {
+        // Point out of range
+        let f = Bipartition::from_blocks(2, &[vec![0, 1, 2, 4]]);
+        // Point in two blocks
+        let g = Bipartition::from_blocks(2, &[vec![0, 1, 2], vec![2, 3]]);
+        // Lookup of the wrong length
+        let h = Bipartition::from_vec(2, vec![0, 1, 2]);
+        assert!(f.is_err());
+        assert!(g.is_err());
+        assert!(h.is_err());
+    }
+
+    #[test]
+    fn normalised() {
+        let f = Bipartition::from_vec(2, vec![5, 3, 3, 5]).unwrap();
+        let g = Bipartition::from_blocks(2, &[vec![0, 3], vec![1, 2]]).unwrap();
+        assert_eq!(f, g);
+        assert_eq!(f.as_vec(), &[0, 1, 1, 0]);
+    }
+
+    #[test]
+    fn multiply() {
+        // {0, 1}, {0', 1'} multiplied by a transposition
+        let e = Bipartition::from_blocks(2, &[vec![0, 1], vec![2, 3]]).unwrap();
+        let t = Bipartition::from_blocks(2, &[vec![0, 3], vec![1, 2]]).unwrap();
+        assert_eq!(e.multiply(&t).unwrap(), e);
+        assert_eq!(t.multiply(&t).unwrap(), Bipartition::id(2));
+        // Floating components are removed
+        let f = Bipartition::from_blocks(2, &[vec![0], vec![1], vec![2, 3]]).unwrap();
+        let g = Bipartition::from_blocks(2, &[vec![0, 1], vec![2], vec![3]]).unwrap();
+        let fg = Bipartition::from_blocks(2, &[vec![0], vec![1], vec![2], vec![3]]).unwrap();
+        assert_eq!(f.multiply(&g).unwrap(), fg);
+        assert!(f.multiply(&Bipartition::id(3)).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let f = Bipartition::from_blocks(2, &[vec![0, 3], vec![1], vec![2]]).unwrap();
+        assert_eq!(f.to_string(), "[{0, 1'}, {1}, {0'}]");
+    }
+}
@@ -0,0 +1,24 @@
+use snafu::Snafu;
+#[derive(Debug, Snafu)]
+pub enum BooleanMatError {
+    #[snafu(display("Invalid rows {:?} for dimension {}", rows, dimension))]
+    InvalidRows {
+        dimension: usize,
+        rows: Vec<Vec<bool>>,
+    },
+    #[snafu(display("Invalid entry ({}, {}) for dimension {}", row, col, dimension))]
+    InvalidEntry {
+        dimension: usize,
+        row: usize,
+        col: usize,
+    },
+    #[snafu(display(
+        "Operation only defined for equal dimension : {} != {}",
+        dimension1,
+        dimension2
+    ))]
+    MismatchingDimension {
+        dimension1: usize,
+        dimension2: usize,
+    },
+}
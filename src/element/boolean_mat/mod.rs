@@ -0,0 +1,363 @@
+use std::sync::Arc;
+
+use super::SemigroupElement;
+use crate::DetHashSet;
+
+pub mod err;
+
+/// Number of bits stored in each block of a row.
+const BLOCK_SIZE: usize = u64::BITS as usize;
+
+/// Representation of a square matrix over the boolean semiring, where addition is or and multiplication is and.
+/// Each row is packed into blocks of bits, with the entry in column j stored in bit j % 64 of block j / 64.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BooleanMat {
+    dimension: usize,
+    bits: Arc<[u64]>,
+}
+
+impl BooleanMat {
+    /// Create a boolean matrix from a vec of rows.
+    /// An error is returned if the matrix is not square of the given dimension.
+    /// ```
+    /// use yatyat::element::boolean_mat::BooleanMat;
+    ///
+    /// assert!(BooleanMat::from_vec(2, vec![vec![true, false], vec![true, true]]).is_ok());
+    /// assert!(BooleanMat::from_vec(2, vec![vec![true, false], vec![true]]).is_err());
+    /// ```
+    pub fn from_vec(dimension: usize, rows: Vec<Vec<bool>>) -> Result<Self, err::BooleanMatError> {
+        if rows.len() != dimension || rows.iter().any(|row| row.len() != dimension) {
+            return Err(err::BooleanMatError::InvalidRows { dimension, rows });
+        }
+        let mut bits = vec![0; dimension * Self::blocks_per_row(dimension)];
+        for (i, row) in rows.iter().enumerate() {
+            for (j, &x) in row.iter().enumerate() {
+                if x {
+                    bits[Self::block_index(dimension, i, j)] |= 1 << (j % BLOCK_SIZE);
+                }
+            }
+        }
+        Ok(Self::from_bits_unchecked(dimension, bits))
+    }
+
+    /// Create a boolean matrix from packed rows. This does not perform the validation.
+    pub(crate) fn from_bits_unchecked(dimension: usize, bits: Vec<u64>) -> Self {
+        Self {
+            dimension,
+            bits: bits.into(),
+        }
+    }
+
+    fn blocks_per_row(dimension: usize) -> usize {
+        dimension.div_ceil(BLOCK_SIZE)
+    }
+
+    fn block_index(dimension: usize, row: usize, col: usize) -> usize {
+        row * Self::blocks_per_row(dimension) + col / BLOCK_SIZE
+    }
+
+    /// Return the packed bits of a row.
+    fn packed_row(&self, row: usize) -> &[u64] {
+        let width = Self::blocks_per_row(self.dimension);
+        &self.bits[row * width..(row + 1) * width]
+    }
+
+    /// Return the identity matrix of the given dimension
+    /// ```
+    /// use yatyat::element::boolean_mat::BooleanMat;
+    ///
+    /// let id = BooleanMat::id(5);
+    /// assert!(id.is_id())
+    /// ```
+    pub fn id(dimension: usize) -> Self {
+        let mut bits = vec![0; dimension * Self::blocks_per_row(dimension)];
+        for i in 0..dimension {
+            bits[Self::block_index(dimension, i, i)] |= 1 << (i % BLOCK_SIZE);
+        }
+        Self::from_bits_unchecked(dimension, bits)
+    }
+
+    /// Return the matrix of the given dimension with every entry false.
+    pub fn zero(dimension: usize) -> Self {
+        Self::from_bits_unchecked(
+            dimension,
+            vec![0; dimension * Self::blocks_per_row(dimension)],
+        )
+    }
+
+    /// Return if a matrix is the identity matrix.
+    pub fn is_id(&self) -> bool {
+        *self == Self::id(self.dimension)
+    }
+
+    /// Return the dimension of the matrix
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Return the entry in the given row and column, or an error if it is out of range.
+    /// ```
+    /// use yatyat::element::boolean_mat::BooleanMat;
+    ///
+    /// let f = BooleanMat::from_vec(2, vec![vec![false, true], vec![false, false]]).unwrap();
+    /// assert!(f.get(0, 1).unwrap());
+    /// assert!(!f.get(1, 1).unwrap());
+    /// assert!(f.get(2, 0).is_err());
+    /// ```
+    pub fn get(&self, row: usize, col: usize) -> Result<bool, err::BooleanMatError> {
+        if row < self.dimension && col < self.dimension {
+            let block = self.bits[Self::block_index(self.dimension, row, col)];
+            Ok(block >> (col % BLOCK_SIZE) & 1 == 1)
+        } else {
+            Err(err::BooleanMatError::InvalidEntry {
+                dimension: self.dimension,
+                row,
+                col,
+            })
+        }
+    }
+
+    /// Return the rows of the matrix.
+    pub fn rows(&self) -> Vec<Vec<bool>> {
+        (0..self.dimension)
+            .map(|i| Self::unpack(self.dimension, self.packed_row(i)))
+            .collect()
+    }
+
+    fn unpack(dimension: usize, row: &[u64]) -> Vec<bool> {
+        (0..dimension)
+            .map(|j| row[j / BLOCK_SIZE] >> (j % BLOCK_SIZE) & 1 == 1)
+            .collect()
+    }
+
+    /// Return the transpose of the matrix.
+    pub fn transpose(&self) -> Self {
+        let mut bits = vec![0; self.bits.len()];
+        for i in 0..self.dimension {
+            for j in 0..self.dimension {
+                if self.get(i, j).unwrap() {
+                    bits[Self::block_index(self.dimension, j, i)] |= 1 << (i % BLOCK_SIZE);
+                }
+            }
+        }
+        Self::from_bits_unchecked(self.dimension, bits)
+    }
+
+    /// Return the row space of the matrix, which is every union of rows including the empty union.
+    /// The rows are returned in increasing order.
+    /// ```
+    /// use yatyat::element::boolean_mat::BooleanMat;
+    ///
+    /// let f = BooleanMat::from_vec(2, vec![vec![true, false], vec![false, true]]).unwrap();
+    /// assert_eq!(f.row_space().len(), 4);
+    /// ```
+    pub fn row_space(&self) -> Vec<Vec<bool>> {
+        let width = Self::blocks_per_row(self.dimension);
+        let zero = vec![0; width];
+        let mut seen = DetHashSet::default();
+        seen.insert(zero.clone());
+        let mut queue = vec![zero];
+        // Take the closure under union with each of the rows
+        while let Some(x) = queue.pop() {
+            for i in 0..self.dimension {
+                let union: Vec<u64> = x
+                    .iter()
+                    .zip(self.packed_row(i))
+                    .map(|(a, b)| a | b)
+                    .collect();
+                if seen.insert(union.clone()) {
+                    queue.push(union);
+                }
+            }
+        }
+        let mut space: Vec<Vec<bool>> = seen
+            .iter()
+            .map(|row| Self::unpack(self.dimension, row))
+            .collect();
+        space.sort_unstable();
+        space
+    }
+
+    /// Return the unique minimal set of rows whose unions give the row space, in increasing order.
+    /// A row is in the basis if it is not zero and is not the union of the rows strictly contained in it.
+    pub fn row_space_basis(&self) -> Vec<Vec<bool>> {
+        let rows: DetHashSet<&[u64]> = (0..self.dimension).map(|i| self.packed_row(i)).collect();
+        let is_subset = |a: &[u64], b: &[u64]| a.iter().zip(b).all(|(x, y)| x & !y == 0);
+        let mut basis: Vec<Vec<bool>> = rows
+            .iter()
+            .filter(|&&row| {
+                let mut union = vec![0; row.len()];
+                for &other in rows
+                    .iter()
+                    .filter(|&&other| other != row && is_subset(other, row))
+                {
+                    union.iter_mut().zip(other).for_each(|(x, y)| *x |= y);
+                }
+                row.iter().any(|&x| x != 0) && union != row
+            })
+            .map(|row| Self::unpack(self.dimension, row))
+            .collect();
+        basis.sort_unstable();
+        basis
+    }
+
+    /// Multiply two matrices over the boolean semiring.
+    /// This is only defined for matrices of the same dimension
+    /// ```
+    /// use yatyat::element::boolean_mat::BooleanMat;
+    ///
+    /// let f = BooleanMat::from_vec(2, vec![vec![false, true], vec![false, false]]).unwrap();
+    /// let g = BooleanMat::from_vec(2, vec![vec![false, false], vec![true, false]]).unwrap();
+    /// let fg = f.multiply(&g).unwrap();
+    /// assert_eq!(fg.rows(), vec![vec![true, false], vec![false, false]]);
+    /// ```
+    pub fn multiply(&self, other: &Self) -> Result<Self, err::BooleanMatError> {
+        if self.dimension != other.dimension {
+            return Err(err::BooleanMatError::MismatchingDimension {
+                dimension1: self.dimension,
+                dimension2: other.dimension,
+            });
+        }
+        let width = Self::blocks_per_row(self.dimension);
+        let mut bits = vec![0; self.bits.len()];
+        // Row i of the product is the union of the rows k of other where self[i][k] is true.
+        for (i, row) in bits
+            .chunks_mut(width.max(1))
+            .enumerate()
+            .take(self.dimension)
+        {
+            for k in (0..self.dimension).filter(|&k| self.get(i, k).unwrap()) {
+                row.iter_mut()
+                    .zip(other.packed_row(k))
+                    .for_each(|(x, y)| *x |= y);
+            }
+        }
+        Ok(Self::from_bits_unchecked(self.dimension, bits))
+    }
+}
+
+impl SemigroupElement for BooleanMat {
+    fn multiply(&self, other: &Self) -> Self {
+        // Will panic if dimensions do not match
+        self.multiply(other).unwrap()
+    }
+
+    fn is_id(&self) -> bool {
+        self.is_id()
+    }
+}
+
+impl std::fmt::Display for BooleanMat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        let mut sep = "";
+        for row in self.rows() {
+            write!(f, "{}[", sep)?;
+            let mut entry_sep = "";
+            for x in row {
+                write!(f, "{}{}", entry_sep, x as u8)?;
+                entry_sep = ", ";
+            }
+            write!(f, "]")?;
+            sep = ", ";
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BooleanMat;
+
+    fn from_ints(rows: &[&[u8]]) -> BooleanMat {
+        let rows: Vec<Vec<bool>> = rows
+            .iter()
+            .map(|row| row.iter().map(|&x| x == 1).collect())
+            .collect();
+        BooleanMat::from_vec(rows.len(), rows).unwrap()
+    }
+
+    #[test]
+    fn id() {
+        let id0 = BooleanMat::id(0);
+        let id100 = BooleanMat::id(100);
+        let f = from_ints(&[&[1, 1], &[0, 1]]);
+        assert!(id0.is_id());
+        assert!(id100.is_id());
+        assert!(!f.is_id());
+        assert!(!BooleanMat::zero(3).is_id());
+    }
+
+    #[test]
+    fn invalid_rows() {
+        // Not square
+        let f = BooleanMat::from_vec(2, vec![vec![true, false, true], vec![true, true, false]]);
+        // Wrong dimension
+        let g = BooleanMat::from_vec(3, vec![vec![true, false], vec![true, true]]);
+        assert!(f.is_err());
+        assert!(g.is_err());
+    }
+
+    #[test]
+    fn multiply() {
+        let f = from_ints(&[&[1, 0, 1], &[0, 1, 0], &[0, 0, 0]]);
+        let g = from_ints(&[&[0, 1, 0], &[0, 0, 1], &[1, 0, 0]]);
+        let fg = from_ints(&[&[1, 1, 0], &[0, 0, 1], &[0, 0, 0]]);
+        assert_eq!(f.multiply(&g).unwrap(), fg);
+        assert_eq!(f.multiply(&BooleanMat::id(3)).unwrap(), f);
+        assert!(f.multiply(&BooleanMat::id(2)).is_err());
+    }
+
+    #[test]
+    fn multiply_large() {
+        // Cyclic shift on 70 points, to test rows spanning multiple blocks.
+        let n = 70;
+        let rows: Vec<Vec<bool>> = (0..n)
+            .map(|i| (0..n).map(|j| j == (i + 1) % n).collect())
+            .collect();
+        let f = BooleanMat::from_vec(n, rows).unwrap();
+        let mut g = f.clone();
+        for _ in 1..n {
+            assert!(!g.is_id());
+            g = g.multiply(&f).unwrap();
+        }
+        assert!(g.is_id());
+        assert_eq!(f.transpose().multiply(&f).unwrap(), BooleanMat::id(n));
+    }
+
+    #[test]
+    fn transpose() {
+        let f = from_ints(&[&[1, 1, 0], &[0, 0, 1], &[0, 0, 0]]);
+        let ft = from_ints(&[&[1, 0, 0], &[1, 0, 0], &[0, 1, 0]]);
+        assert_eq!(f.transpose(), ft);
+        assert_eq!(ft.transpose(), f);
+    }
+
+    #[test]
+    fn row_space() {
+        let f = from_ints(&[&[1, 1, 0], &[0, 1, 0], &[1, 1, 0]]);
+        let space = f.row_space();
+        assert_eq!(
+            space,
+            vec![
+                vec![false, false, false],
+                vec![false, true, false],
+                vec![true, true, false]
+            ]
+        );
+        assert_eq!(
+            f.row_space_basis(),
+            vec![vec![false, true, false], vec![true, true, false]]
+        );
+        // The union of the first two rows is not in the basis
+        let g = from_ints(&[&[1, 0, 0], &[0, 1, 0], &[1, 1, 0]]);
+        assert_eq!(g.row_space().len(), 4);
+        assert_eq!(g.row_space_basis().len(), 2);
+    }
+
+    #[test]
+    fn display() {
+        let f = from_ints(&[&[1, 0], &[1, 1]]);
+        assert_eq!(f.to_string(), "[[1, 0], [1, 1]]");
+    }
+}
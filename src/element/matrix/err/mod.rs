@@ -0,0 +1,25 @@
+use snafu::Snafu;
+#[derive(Debug, Snafu)]
+pub enum MatrixError {
+    #[snafu(display("Rows do not form a square matrix of dimension {}", dimension))]
+    InvalidRows { dimension: usize },
+    #[snafu(display("Entry ({}, {}) is not in the semiring", row, col))]
+    NotInSemiring { row: usize, col: usize },
+    #[snafu(display("Invalid entry ({}, {}) for dimension {}", row, col, dimension))]
+    InvalidEntry {
+        dimension: usize,
+        row: usize,
+        col: usize,
+    },
+    #[snafu(display(
+        "Operation only defined for equal dimension : {} != {}",
+        dimension1,
+        dimension2
+    ))]
+    MismatchingDimension {
+        dimension1: usize,
+        dimension2: usize,
+    },
+    #[snafu(display("Operation only defined for matrices over the same semiring"))]
+    MismatchingSemiring {},
+}
@@ -0,0 +1,272 @@
+use std::sync::Arc;
+
+use super::SemigroupElement;
+
+pub mod err;
+pub mod semiring;
+
+use semiring::Semiring;
+
+/// Representation of a square matrix over a semiring.
+/// The entries are stored in a single vector, row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix<S>
+where
+    S: Semiring,
+{
+    semiring: S,
+    dimension: usize,
+    entries: Arc<[S::Scalar]>,
+}
+
+impl<S> Matrix<S>
+where
+    S: Semiring,
+{
+    /// Create a matrix over a semiring from a vec of rows.
+    /// An error is returned if the matrix is not square of the given dimension, or an entry is not in the semiring.
+    /// ```
+    /// use yatyat::element::matrix::{semiring::MaxPlusTruncated, Matrix};
+    ///
+    /// let s = MaxPlusTruncated::new(3);
+    /// assert!(Matrix::from_vec(s, 2, vec![vec![Some(1), None], vec![Some(0), Some(3)]]).is_ok());
+    /// assert!(Matrix::from_vec(s, 2, vec![vec![Some(1), None], vec![Some(0), Some(4)]]).is_err());
+    /// ```
+    pub fn from_vec(
+        semiring: S,
+        dimension: usize,
+        rows: Vec<Vec<S::Scalar>>,
+    ) -> Result<Self, err::MatrixError> {
+        if rows.len() != dimension || rows.iter().any(|row| row.len() != dimension) {
+            return Err(err::MatrixError::InvalidRows { dimension });
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if let Some(j) = row.iter().position(|x| !semiring.contains(x)) {
+                return Err(err::MatrixError::NotInSemiring { row: i, col: j });
+            }
+        }
+        let entries = rows.into_iter().flatten().collect();
+        Ok(Self::from_vec_unchecked(semiring, dimension, entries))
+    }
+
+    /// Create a matrix given the entries row by row. This does not perform the validation.
+    pub(crate) fn from_vec_unchecked(
+        semiring: S,
+        dimension: usize,
+        entries: Vec<S::Scalar>,
+    ) -> Self {
+        Self {
+            semiring,
+            dimension,
+            entries: entries.into(),
+        }
+    }
+
+    /// Return the identity matrix over the semiring of the given dimension
+    /// ```
+    /// use yatyat::element::matrix::{semiring::MinPlus, Matrix};
+    ///
+    /// let id = Matrix::id(MinPlus, 4);
+    /// assert!(id.is_id())
+    /// ```
+    pub fn id(semiring: S, dimension: usize) -> Self {
+        let entries = (0..dimension * dimension)
+            .map(|x| {
+                if x / dimension == x % dimension {
+                    semiring.one()
+                } else {
+                    semiring.zero()
+                }
+            })
+            .collect();
+        Self::from_vec_unchecked(semiring, dimension, entries)
+    }
+
+    /// Return if a matrix is the identity matrix.
+    pub fn is_id(&self) -> bool {
+        *self == Self::id(self.semiring.clone(), self.dimension)
+    }
+
+    /// Return the dimension of the matrix
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Return the semiring the matrix is defined over
+    pub fn semiring(&self) -> &S {
+        &self.semiring
+    }
+
+    /// Return the entry in the given row and column, or an error if it is out of range.
+    pub fn get(&self, row: usize, col: usize) -> Result<&S::Scalar, err::MatrixError> {
+        if row < self.dimension && col < self.dimension {
+            Ok(&self.entries[row * self.dimension + col])
+        } else {
+            Err(err::MatrixError::InvalidEntry {
+                dimension: self.dimension,
+                row,
+                col,
+            })
+        }
+    }
+
+    /// Return the rows of the matrix.
+    pub fn rows(&self) -> Vec<Vec<S::Scalar>> {
+        self.entries
+            .chunks(self.dimension.max(1))
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    /// Return the transpose of the matrix.
+    pub fn transpose(&self) -> Self {
+        let n = self.dimension;
+        let entries = (0..n * n)
+            .map(|x| self.entries[(x % n) * n + x / n].clone())
+            .collect();
+        Self::from_vec_unchecked(self.semiring.clone(), n, entries)
+    }
+
+    /// Multiply two matrices over the semiring.
+    /// This is only defined for matrices of the same dimension over the same semiring
+    /// ```
+    /// use yatyat::element::matrix::{semiring::IntegersMod, Matrix};
+    ///
+    /// let s = IntegersMod::new(3);
+    /// let f = Matrix::from_vec(s, 2, vec![vec![1, 1], vec![0, 1]]).unwrap();
+    /// let f2 = f.multiply(&f).unwrap();
+    /// assert_eq!(f2.rows(), vec![vec![1, 2], vec![0, 1]]);
+    /// ```
+    pub fn multiply(&self, other: &Self) -> Result<Self, err::MatrixError> {
+        if self.dimension != other.dimension {
+            return Err(err::MatrixError::MismatchingDimension {
+                dimension1: self.dimension,
+                dimension2: other.dimension,
+            });
+        }
+        if self.semiring != other.semiring {
+            return Err(err::MatrixError::MismatchingSemiring {});
+        }
+        let n = self.dimension;
+        let s = &self.semiring;
+        let entries = (0..n * n)
+            .map(|x| {
+                let (i, j) = (x / n, x % n);
+                (0..n).fold(s.zero(), |acc, k| {
+                    let product = s.prod(&self.entries[i * n + k], &other.entries[k * n + j]);
+                    s.plus(&acc, &product)
+                })
+            })
+            .collect();
+        Ok(Self::from_vec_unchecked(s.clone(), n, entries))
+    }
+}
+
+impl<S> SemigroupElement for Matrix<S>
+where
+    S: Semiring,
+{
+    fn multiply(&self, other: &Self) -> Self {
+        // Will panic if dimensions or semirings do not match
+        self.multiply(other).unwrap()
+    }
+
+    fn is_id(&self) -> bool {
+        self.is_id()
+    }
+}
+
+impl<S> std::fmt::Display for Matrix<S>
+where
+    S: Semiring,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[")?;
+        let mut sep = "";
+        for row in self.entries.chunks(self.dimension.max(1)) {
+            write!(f, "{}[", sep)?;
+            let mut entry_sep = "";
+            for x in row {
+                write!(f, "{}", entry_sep)?;
+                self.semiring.fmt_scalar(x, f)?;
+                entry_sep = ", ";
+            }
+            write!(f, "]")?;
+            sep = ", ";
+        }
+        write!(f, "]")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        semiring::{IntegersMod, MaxPlus, MaxPlusTruncated, NaturalTruncated},
+        Matrix,
+    };
+
+    #[test]
+    fn id() {
+        assert!(Matrix::id(MaxPlus, 0).is_id());
+        assert!(Matrix::id(NaturalTruncated::new(2, 3), 5).is_id());
+        let f = Matrix::from_vec(
+            MaxPlus,
+            2,
+            vec![vec![Some(0), None], vec![Some(0), Some(0)]],
+        );
+        assert!(!f.unwrap().is_id());
+    }
+
+    #[test]
+    fn invalid_rows() {
+        let s = IntegersMod::new(4);
+        assert!(Matrix::from_vec(s, 2, vec![vec![1, 2], vec![3]]).is_err());
+        assert!(Matrix::from_vec(s, 3, vec![vec![1, 2], vec![3, 0]]).is_err());
+        assert!(Matrix::from_vec(s, 2, vec![vec![1, 2], vec![3, 4]]).is_err());
+    }
+
+    #[test]
+    fn multiply() {
+        let f = Matrix::from_vec(
+            MaxPlus,
+            2,
+            vec![vec![Some(1), Some(-2)], vec![None, Some(3)]],
+        )
+        .unwrap();
+        let f2 = Matrix::from_vec(
+            MaxPlus,
+            2,
+            vec![vec![Some(2), Some(1)], vec![None, Some(6)]],
+        )
+        .unwrap();
+        assert_eq!(f.multiply(&f).unwrap(), f2);
+        assert_eq!(f.multiply(&Matrix::id(MaxPlus, 2)).unwrap(), f);
+        assert!(f.multiply(&Matrix::id(MaxPlus, 3)).is_err());
+    }
+
+    #[test]
+    fn mismatching_semiring() {
+        let f = Matrix::id(MaxPlusTruncated::new(3), 2);
+        let g = Matrix::id(MaxPlusTruncated::new(4), 2);
+        assert!(f.multiply(&g).is_err());
+    }
+
+    #[test]
+    fn transpose() {
+        let s = IntegersMod::new(7);
+        let f = Matrix::from_vec(s, 2, vec![vec![1, 2], vec![3, 4]]).unwrap();
+        let ft = Matrix::from_vec(s, 2, vec![vec![1, 3], vec![2, 4]]).unwrap();
+        assert_eq!(f.transpose(), ft);
+    }
+
+    #[test]
+    fn display() {
+        let f = Matrix::from_vec(
+            MaxPlusTruncated::new(2),
+            2,
+            vec![vec![Some(1), None], vec![Some(0), Some(2)]],
+        )
+        .unwrap();
+        assert_eq!(f.to_string(), "[[1, -∞], [0, 2]]");
+    }
+}
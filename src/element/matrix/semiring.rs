@@ -0,0 +1,384 @@
+use std::{fmt::Debug, hash::Hash};
+
+/// Trait to represent a semiring that matrices can be defined over.
+///
+/// The semiring is a value rather than just a type, so that parameters such as a threshold can be chosen at runtime.
+/// Addition must be associative and commutative with identity zero, and multiplication must be associative with identity one and distribute over addition, but this is not checked.
+pub trait Semiring: Clone + Eq + Hash + Debug {
+    /// The type of the elements of the semiring.
+    type Scalar: Clone + Eq + Hash + Debug;
+    /// Return the additive identity
+    fn zero(&self) -> Self::Scalar;
+    /// Return the multiplicative identity
+    fn one(&self) -> Self::Scalar;
+    /// Add two elements of the semiring
+    fn plus(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar;
+    /// Multiply two elements of the semiring
+    fn prod(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar;
+    /// Return if a value is an element of this semiring.
+    fn contains(&self, _x: &Self::Scalar) -> bool {
+        true
+    }
+    /// Format an element of the semiring.
+    fn fmt_scalar(&self, x: &Self::Scalar, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+}
+
+/// The max-plus semiring on the integers with negative infinity, where addition is max and multiplication is +.
+/// Negative infinity is represented by None, and products saturate at the bounds of i64 rather than overflowing,
+/// so multiplication is only associative while the sums of the entries stay within these bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MaxPlus;
+
+impl Semiring for MaxPlus {
+    type Scalar = Option<i64>;
+
+    fn zero(&self) -> Self::Scalar {
+        None
+    }
+
+    fn one(&self) -> Self::Scalar {
+        Some(0)
+    }
+
+    fn plus(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        // None is less than all other values.
+        *x.max(y)
+    }
+
+    fn prod(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        Some(x.as_ref()?.saturating_add(*y.as_ref()?))
+    }
+
+    fn fmt_scalar(&self, x: &Self::Scalar, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match x {
+            Some(x) => write!(f, "{}", x),
+            None => write!(f, "-∞"),
+        }
+    }
+}
+
+/// The min-plus semiring on the integers with infinity, where addition is min and multiplication is +.
+/// Infinity is represented by None, and products saturate at the bounds of i64 rather than overflowing,
+/// so multiplication is only associative while the sums of the entries stay within these bounds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct MinPlus;
+
+impl Semiring for MinPlus {
+    type Scalar = Option<i64>;
+
+    fn zero(&self) -> Self::Scalar {
+        None
+    }
+
+    fn one(&self) -> Self::Scalar {
+        Some(0)
+    }
+
+    fn plus(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        match (x, y) {
+            (Some(x), Some(y)) => Some(*x.min(y)),
+            (Some(x), None) | (None, Some(x)) => Some(*x),
+            (None, None) => None,
+        }
+    }
+
+    fn prod(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        Some(x.as_ref()?.saturating_add(*y.as_ref()?))
+    }
+
+    fn fmt_scalar(&self, x: &Self::Scalar, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match x {
+            Some(x) => write!(f, "{}", x),
+            None => write!(f, "∞"),
+        }
+    }
+}
+
+/// The tropical max-plus semiring on {-∞, 0, ..., threshold}, where addition is max and multiplication is + truncated at the threshold.
+/// Negative infinity is represented by None.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaxPlusTruncated {
+    threshold: usize,
+}
+
+impl MaxPlusTruncated {
+    /// Create the semiring with the given threshold.
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold }
+    }
+
+    /// Return the threshold of the semiring
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl Semiring for MaxPlusTruncated {
+    type Scalar = Option<usize>;
+
+    fn zero(&self) -> Self::Scalar {
+        None
+    }
+
+    fn one(&self) -> Self::Scalar {
+        Some(0)
+    }
+
+    fn plus(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        // None is less than all other values.
+        *x.max(y)
+    }
+
+    fn prod(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        Some(x.as_ref()?.saturating_add(*y.as_ref()?).min(self.threshold))
+    }
+
+    fn contains(&self, x: &Self::Scalar) -> bool {
+        x.map_or(true, |x| x <= self.threshold)
+    }
+
+    fn fmt_scalar(&self, x: &Self::Scalar, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match x {
+            Some(x) => write!(f, "{}", x),
+            None => write!(f, "-∞"),
+        }
+    }
+}
+
+/// The tropical min-plus semiring on {0, ..., threshold, ∞}, where addition is min and multiplication is + truncated at the threshold.
+/// Infinity is represented by None.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MinPlusTruncated {
+    threshold: usize,
+}
+
+impl MinPlusTruncated {
+    /// Create the semiring with the given threshold.
+    pub fn new(threshold: usize) -> Self {
+        Self { threshold }
+    }
+
+    /// Return the threshold of the semiring
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+}
+
+impl Semiring for MinPlusTruncated {
+    type Scalar = Option<usize>;
+
+    fn zero(&self) -> Self::Scalar {
+        None
+    }
+
+    fn one(&self) -> Self::Scalar {
+        Some(0)
+    }
+
+    fn plus(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        match (x, y) {
+            (Some(x), Some(y)) => Some(*x.min(y)),
+            (Some(x), None) | (None, Some(x)) => Some(*x),
+            (None, None) => None,
+        }
+    }
+
+    fn prod(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        Some(x.as_ref()?.saturating_add(*y.as_ref()?).min(self.threshold))
+    }
+
+    fn contains(&self, x: &Self::Scalar) -> bool {
+        x.map_or(true, |x| x <= self.threshold)
+    }
+
+    fn fmt_scalar(&self, x: &Self::Scalar, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match x {
+            Some(x) => write!(f, "{}", x),
+            None => write!(f, "∞"),
+        }
+    }
+}
+
+/// The natural numbers quotiented by threshold = threshold + period, with the usual addition and multiplication.
+/// The elements are 0, ..., threshold + period - 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NaturalTruncated {
+    threshold: usize,
+    period: usize,
+}
+
+impl NaturalTruncated {
+    /// Create the semiring with the given threshold and period.
+    /// This will panic if the period is zero, or if the largest element threshold + period - 1 does not fit in a usize.
+    pub fn new(threshold: usize, period: usize) -> Self {
+        assert!(period > 0, "Period must be positive");
+        assert!(
+            threshold.checked_add(period - 1).is_some(),
+            "Threshold + period - 1 must fit in a usize"
+        );
+        Self { threshold, period }
+    }
+
+    /// Return the threshold of the semiring
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Return the period of the semiring
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Reduce a natural number to its representative in the semiring.
+    /// This takes a u128 so that sums and products of elements can be reduced without overflowing.
+    fn reduce(&self, x: u128) -> usize {
+        let threshold = self.threshold as u128;
+        if x < threshold {
+            x as usize
+        } else {
+            self.threshold + ((x - threshold) % self.period as u128) as usize
+        }
+    }
+}
+
+impl Semiring for NaturalTruncated {
+    type Scalar = usize;
+
+    fn zero(&self) -> Self::Scalar {
+        0
+    }
+
+    fn one(&self) -> Self::Scalar {
+        self.reduce(1)
+    }
+
+    fn plus(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        self.reduce(*x as u128 + *y as u128)
+    }
+
+    fn prod(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        self.reduce(*x as u128 * *y as u128)
+    }
+
+    fn contains(&self, x: &Self::Scalar) -> bool {
+        // If the sum overflows, then every usize is at most the largest element.
+        self.threshold
+            .checked_add(self.period)
+            .map_or(true, |n| *x < n)
+    }
+
+    fn fmt_scalar(&self, x: &Self::Scalar, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", x)
+    }
+}
+
+/// The integers modulo n, with elements 0, ..., n - 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct IntegersMod {
+    modulus: usize,
+}
+
+impl IntegersMod {
+    /// Create the integers modulo n.
+    /// This will panic if the modulus is zero.
+    pub fn new(modulus: usize) -> Self {
+        assert!(modulus > 0, "Modulus must be positive");
+        Self { modulus }
+    }
+
+    /// Return the modulus of the semiring
+    pub fn modulus(&self) -> usize {
+        self.modulus
+    }
+}
+
+impl Semiring for IntegersMod {
+    type Scalar = usize;
+
+    fn zero(&self) -> Self::Scalar {
+        0
+    }
+
+    fn one(&self) -> Self::Scalar {
+        1 % self.modulus
+    }
+
+    fn plus(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        ((*x as u128 + *y as u128) % self.modulus as u128) as usize
+    }
+
+    fn prod(&self, x: &Self::Scalar, y: &Self::Scalar) -> Self::Scalar {
+        // Multiply as u128 so that large moduli do not overflow.
+        ((*x as u128 * *y as u128) % self.modulus as u128) as usize
+    }
+
+    fn contains(&self, x: &Self::Scalar) -> bool {
+        *x < self.modulus
+    }
+
+    fn fmt_scalar(&self, x: &Self::Scalar, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn max_plus() {
+        let s = MaxPlus;
+        assert_eq!(s.plus(&Some(-3), &None), Some(-3));
+        assert_eq!(s.prod(&Some(-3), &Some(5)), Some(2));
+        assert_eq!(s.prod(&Some(-3), &None), None);
+        assert_eq!(s.prod(&Some(i64::MAX), &Some(1)), Some(i64::MAX));
+    }
+
+    #[test]
+    fn min_plus() {
+        let s = MinPlus;
+        assert_eq!(s.plus(&Some(-3), &None), Some(-3));
+        assert_eq!(s.plus(&Some(-3), &Some(1)), Some(-3));
+        assert_eq!(s.prod(&Some(1), &None), None);
+        assert_eq!(s.prod(&Some(i64::MIN), &Some(-1)), Some(i64::MIN));
+    }
+
+    #[test]
+    fn truncated() {
+        let s = MaxPlusTruncated::new(4);
+        assert_eq!(s.prod(&Some(3), &Some(3)), Some(4));
+        assert_eq!(s.plus(&Some(3), &None), Some(3));
+        assert!(!s.contains(&Some(5)));
+        let s = MinPlusTruncated::new(4);
+        assert_eq!(s.prod(&Some(3), &Some(3)), Some(4));
+        assert_eq!(s.plus(&Some(3), &None), Some(3));
+        assert!(s.contains(&None));
+        let s = MaxPlusTruncated::new(usize::MAX);
+        assert_eq!(s.prod(&Some(usize::MAX), &Some(1)), Some(usize::MAX));
+    }
+
+    #[test]
+    fn natural_truncated() {
+        let s = NaturalTruncated::new(3, 2);
+        // 2 * 2 = 4 and 2 * 3 = 6 = 4
+        assert_eq!(s.prod(&2, &2), 4);
+        assert_eq!(s.prod(&2, &3), 4);
+        assert_eq!(s.plus(&4, &4), 4);
+        assert_eq!(s.plus(&4, &1), 3);
+        assert!(!s.contains(&5));
+        let s = IntegersMod::new(5);
+        assert_eq!(s.prod(&3, &4), 2);
+        assert_eq!(s.plus(&3, &4), 2);
+        assert_eq!(IntegersMod::new(1).one(), 0);
+        // Large entries must not overflow.
+        let s = IntegersMod::new(usize::MAX);
+        assert_eq!(s.prod(&(usize::MAX - 1), &(usize::MAX - 1)), 1);
+        assert_eq!(s.plus(&(usize::MAX - 1), &2), 1);
+        let s = NaturalTruncated::new(usize::MAX - 1, 1);
+        assert_eq!(s.prod(&(usize::MAX - 1), &(usize::MAX - 1)), usize::MAX - 1);
+        assert!(!s.contains(&usize::MAX));
+        let s = NaturalTruncated::new(usize::MAX, 1);
+        assert!(s.contains(&usize::MAX));
+        assert_eq!(s.plus(&usize::MAX, &1), usize::MAX);
+    }
+}
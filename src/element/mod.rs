@@ -1,3 +1,8 @@
+pub mod bipartition;
+pub mod boolean_mat;
+pub mod matrix;
+pub mod partial_perm;
+pub mod permutation;
 pub mod transformation;
 
 pub trait SemigroupElement: Clone + Eq {
@@ -0,0 +1,330 @@
+use std::sync::Arc;
+
+use super::SemigroupElement;
+
+pub mod err;
+
+/// Representation of a partial permutation on the points 0..n-1
+/// This is stored as a vector using the images of each point from 0..n-1, where None means the point is not in the domain.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PartialPerm {
+    degree: usize,
+    vals: Arc<[Option<usize>]>,
+}
+
+impl PartialPerm {
+    pub fn as_vec(&self) -> &[Option<usize>] {
+        &self.vals[..]
+    }
+
+    /// Create partial permutation from vec of images, where None means the point is not in the domain.
+    /// An error is returned if the images are not defined on the given degree or are not distinct.
+    /// ```
+    /// use yatyat::element::partial_perm::PartialPerm;
+    ///
+    /// assert!(PartialPerm::from_vec(3, vec![Some(2), None, Some(0)]).is_ok());
+    /// assert!(PartialPerm::from_vec(3, vec![Some(2), None, Some(2)]).is_err());
+    /// ```
+    pub fn from_vec(
+        degree: usize,
+        vals: Vec<Option<usize>>,
+    ) -> Result<Self, err::PartialPermError> {
+        let mut seen = vec![false; degree];
+        let valid = vals.len() == degree
+            && vals.iter().flatten().all(|&x| {
+                // Each image must be in range and not seen before
+                x < degree && !std::mem::replace(&mut seen[x], true)
+            });
+        if valid {
+            Ok(PartialPerm::from_vec_unchecked(degree, vals))
+        } else {
+            Err(err::PartialPermError::InvalidImage {
+                degree,
+                image: vals,
+            })
+        }
+    }
+
+    /// Create a partial permutation mapping domain[i] to image[i].
+    /// An error is returned if the result is not a valid partial permutation on the given degree.
+    /// ```
+    /// use yatyat::element::partial_perm::PartialPerm;
+    ///
+    /// let f = PartialPerm::from_domain_image(4, &[0, 3], &[1, 2]).unwrap();
+    /// assert_eq!(f.as_vec(), &[Some(1), None, None, Some(2)]);
+    /// ```
+    pub fn from_domain_image(
+        degree: usize,
+        domain: &[usize],
+        image: &[usize],
+    ) -> Result<Self, err::PartialPermError> {
+        let mut vals = vec![None; degree];
+        let invalid = || err::PartialPermError::InvalidImage {
+            degree,
+            image: image.iter().copied().map(Some).collect(),
+        };
+        if domain.len() != image.len() {
+            return Err(invalid());
+        }
+        for (&x, &y) in domain.iter().zip(image) {
+            match vals.get_mut(x) {
+                Some(val @ None) => *val = Some(y),
+                // Point is either out of range or repeated in the domain
+                _ => return Err(invalid()),
+            }
+        }
+        PartialPerm::from_vec(degree, vals)
+    }
+
+    /// Create a PartialPerm given an image. This does not perform the validation.
+    pub(crate) fn from_vec_unchecked(degree: usize, vals: Vec<Option<usize>>) -> Self {
+        Self {
+            degree,
+            vals: vals.into(),
+        }
+    }
+
+    /// Return the identity partial permutation on degree points
+    /// ```
+    /// use yatyat::element::partial_perm::PartialPerm;
+    ///
+    /// let id = PartialPerm::id(5);
+    /// assert!(id.is_id())
+    /// ```
+    pub fn id(degree: usize) -> Self {
+        Self::from_vec_unchecked(degree, (0..degree).map(Some).collect())
+    }
+
+    /// Return the empty partial permutation on degree points, which has an empty domain.
+    pub fn empty(degree: usize) -> Self {
+        Self::from_vec_unchecked(degree, vec![None; degree])
+    }
+
+    /// Return if a partial permutation is the identity partial permutation.
+    pub fn is_id(&self) -> bool {
+        self.vals.iter().enumerate().all(|(i, x)| Some(i) == *x)
+    }
+
+    /// Return the degree of the partial permutation
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Apply the partial permutation to a given point.
+    /// If x is less than the degree, then the image of x is returned, which is None if x is not in the domain.
+    /// Otherwise an error is returned.
+    /// ```
+    /// use yatyat::element::partial_perm::PartialPerm;
+    ///
+    /// let f = PartialPerm::from_vec(3, vec![Some(2), None, Some(0)]).unwrap();
+    /// assert_eq!(Some(0), f.apply(2).unwrap());
+    /// assert_eq!(None, f.apply(1).unwrap());
+    /// assert!(f.apply(5).is_err())
+    /// ```
+    pub fn apply(&self, x: usize) -> Result<Option<usize>, err::PartialPermError> {
+        if x < self.degree {
+            Ok(self.vals[x])
+        } else {
+            Err(err::PartialPermError::InvalidPoint {
+                degree: self.degree,
+                point: x,
+            })
+        }
+    }
+
+    /// Return the points in the domain in increasing order.
+    pub fn domain(&self) -> Vec<usize> {
+        self.vals
+            .iter()
+            .enumerate()
+            .filter_map(|(i, x)| x.map(|_| i))
+            .collect()
+    }
+
+    /// Return the points in the image in increasing order.
+    pub fn image(&self) -> Vec<usize> {
+        let mut image: Vec<usize> = self.vals.iter().flatten().copied().collect();
+        image.sort_unstable();
+        image
+    }
+
+    /// Return the rank, which is the size of the domain.
+    pub fn rank(&self) -> usize {
+        self.vals.iter().flatten().count()
+    }
+
+    /// Return the inverse partial permutation, which maps the image back onto the domain.
+    /// ```
+    /// use yatyat::element::partial_perm::PartialPerm;
+    ///
+    /// let f = PartialPerm::from_vec(3, vec![Some(2), None, Some(0)]).unwrap();
+    /// let g = f.inverse();
+    /// assert_eq!(g.as_vec(), &[Some(2), None, Some(0)]);
+    /// assert_eq!(f.multiply(&g).unwrap().domain(), f.domain());
+    /// ```
+    pub fn inverse(&self) -> Self {
+        let mut vals = vec![None; self.degree];
+        for (i, x) in self.vals.iter().enumerate() {
+            if let Some(x) = x {
+                vals[*x] = Some(i);
+            }
+        }
+        Self::from_vec_unchecked(self.degree, vals)
+    }
+
+    /// Compose two partial permutations, applying self first and then other.
+    /// A point is in the domain of the product if it is mapped by self into the domain of other.
+    /// This is only defined for partial permutations of the same degree
+    /// ```
+    /// use yatyat::element::partial_perm::PartialPerm;
+    ///
+    /// let f = PartialPerm::from_vec(3, vec![Some(1), Some(2), None]).unwrap();
+    /// let g = PartialPerm::from_vec(3, vec![None, Some(0), Some(1)]).unwrap();
+    /// let fg = f.multiply(&g).unwrap();
+    /// assert_eq!(fg.as_vec(), &[Some(0), Some(1), None]);
+    /// ```
+    pub fn multiply(&self, other: &Self) -> Result<Self, err::PartialPermError> {
+        if self.degree == other.degree {
+            let vals = self
+                .vals
+                .iter()
+                .map(|x| x.and_then(|y| other.vals[y]))
+                .collect();
+            Ok(PartialPerm::from_vec_unchecked(self.degree, vals))
+        } else {
+            Err(err::PartialPermError::MismatchingDegree {
+                degree1: self.degree,
+                degree2: other.degree,
+            })
+        }
+    }
+}
+
+impl SemigroupElement for PartialPerm {
+    fn multiply(&self, other: &Self) -> Self {
+        // Will panic if degrees do not match
+        self.multiply(other).unwrap()
+    }
+
+    fn is_id(&self) -> bool {
+        self.is_id()
+    }
+}
+
+/// Display in chain and cycle notation.
+/// Chains start at a point not in the image and end at a point not in the domain, and are written as [0 1 2].
+/// The remaining points in the domain lie on cycles, which are written as (3 4).
+impl std::fmt::Display for PartialPerm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.rank() == 0 {
+            return write!(f, "<empty partial perm>");
+        }
+        let mut seen = vec![false; self.degree];
+        let mut in_image = vec![false; self.degree];
+        for x in self.vals.iter().flatten() {
+            in_image[*x] = true;
+        }
+        // First write out the chains
+        for start in (0..self.degree).filter(|&x| !in_image[x] && self.vals[x].is_some()) {
+            write!(f, "[{}", start)?;
+            seen[start] = true;
+            let mut cur = self.vals[start];
+            while let Some(x) = cur {
+                write!(f, " {}", x)?;
+                seen[x] = true;
+                cur = self.vals[x];
+            }
+            write!(f, "]")?;
+        }
+        // Then the cycles, which are all remaining points in the domain.
+        for start in 0..self.degree {
+            if seen[start] || self.vals[start].is_none() {
+                continue;
+            }
+            write!(f, "({}", start)?;
+            seen[start] = true;
+            let mut cur = self.vals[start].unwrap();
+            while cur != start {
+                write!(f, " {}", cur)?;
+                seen[cur] = true;
+                cur = self.vals[cur].unwrap();
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartialPerm;
+
+    #[test]
+    fn id() {
+        let id0 = PartialPerm::id(0);
+        let id10 = PartialPerm::id(10);
+        let f = PartialPerm::from_vec(2, vec![Some(0), None]).unwrap();
+        assert!(id0.is_id());
+        assert!(id10.is_id());
+        assert!(!f.is_id());
+        assert!(!PartialPerm::empty(3).is_id());
+    }
+
+    #[test]
+    fn invalid_image() {
+        // Invalid for value out of range
+        let f = PartialPerm::from_vec(3, vec![Some(0), None, Some(4)]);
+        // Invalid for too many values
+        let g = PartialPerm::from_vec(4, vec![Some(1), Some(2), Some(3)]);
+        // Invalid as not injective
+        let h = PartialPerm::from_vec(3, vec![Some(1), Some(1), None]);
+        // Invalid as point repeated in domain
+        let k = PartialPerm::from_domain_image(3, &[0, 0], &[1, 2]);
+        assert!(f.is_err());
+        assert!(g.is_err());
+        assert!(h.is_err());
+        assert!(k.is_err());
+    }
+
+    #[test]
+    fn domain_image_rank() {
+        let f = PartialPerm::from_domain_image(5, &[4, 0, 2], &[1, 3, 0]).unwrap();
+        assert_eq!(f.domain(), vec![0, 2, 4]);
+        assert_eq!(f.image(), vec![0, 1, 3]);
+        assert_eq!(f.rank(), 3);
+        assert_eq!(PartialPerm::empty(5).rank(), 0);
+    }
+
+    #[test]
+    fn multiply_inverse() {
+        let f = PartialPerm::from_vec(4, vec![Some(3), None, Some(1), Some(0)]).unwrap();
+        let f_inv = f.inverse();
+        // f * f^-1 is the identity on the domain of f
+        let ff_inv = f.multiply(&f_inv).unwrap();
+        assert_eq!(ff_inv.domain(), f.domain());
+        assert!(ff_inv
+            .domain()
+            .iter()
+            .all(|&x| ff_inv.apply(x).unwrap() == Some(x)));
+        // f * f^-1 * f = f
+        assert_eq!(ff_inv.multiply(&f).unwrap(), f);
+        assert_eq!(f_inv.inverse(), f);
+    }
+
+    #[test]
+    fn multiply() {
+        let f = PartialPerm::from_vec(4, vec![Some(2), None, Some(3), Some(1)]).unwrap();
+        let g = PartialPerm::from_vec(4, vec![Some(2), Some(1), None, Some(3)]).unwrap();
+        let fg = PartialPerm::from_vec(4, vec![None, None, Some(3), Some(1)]).unwrap();
+        assert_eq!(fg, f.multiply(&g).unwrap());
+        assert!(f.multiply(&PartialPerm::id(3)).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let f = PartialPerm::from_vec(6, vec![Some(1), Some(2), None, Some(4), Some(3), Some(5)])
+            .unwrap();
+        assert_eq!(f.to_string(), "[0 1 2](3 4)(5)");
+        assert_eq!(PartialPerm::empty(2).to_string(), "<empty partial perm>");
+    }
+}
@@ -0,0 +1,17 @@
+use snafu::Snafu;
+#[derive(Debug, Snafu)]
+pub enum PermutationError {
+    #[snafu(display("Invalid image {:?} for degree {}", image, degree))]
+    InvalidImage { degree: usize, image: Vec<usize> },
+    #[snafu(display("Invalid cycles {:?} for degree {}", cycles, degree))]
+    InvalidCycles {
+        degree: usize,
+        cycles: Vec<Vec<usize>>,
+    },
+    #[snafu(display("Invalid point {} for degree {}", point, degree))]
+    InvalidPoint { degree: usize, point: usize },
+    #[snafu(display("Operation only defined for equal degree : {} != {}", degree1, degree2))]
+    MismatchingDegree { degree1: usize, degree2: usize },
+    #[snafu(display("Could not parse cycle notation: {}", input))]
+    InvalidCycleNotation { input: String },
+}
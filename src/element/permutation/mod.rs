@@ -0,0 +1,393 @@
+use std::{convert::TryFrom, str::FromStr, sync::Arc};
+
+use super::{transformation::Transformation, SemigroupElement};
+
+pub mod err;
+
+/// Representation of a permutation on the points 0..n-1
+/// This is stored as a vector using the images of each point from 0..n-1, which must be a bijection.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Permutation {
+    degree: usize,
+    vals: Arc<[usize]>,
+}
+
+impl Permutation {
+    pub fn as_vec(&self) -> &[usize] {
+        &self.vals[..]
+    }
+
+    /// Create permutation from vec of images
+    /// An error is returned if the images are not a bijection on the given degree.
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// assert!(Permutation::from_vec(3, vec![2, 0, 1]).is_ok());
+    /// assert!(Permutation::from_vec(3, vec![2, 0, 0]).is_err());
+    /// ```
+    pub fn from_vec(degree: usize, vals: Vec<usize>) -> Result<Self, err::PermutationError> {
+        let mut seen = vec![false; degree];
+        let valid = vals.len() == degree
+            && vals
+                .iter()
+                .all(|&x| x < degree && !std::mem::replace(&mut seen[x], true));
+        if valid {
+            Ok(Permutation::from_vec_unchecked(degree, vals))
+        } else {
+            Err(err::PermutationError::InvalidImage {
+                degree,
+                image: vals,
+            })
+        }
+    }
+
+    /// Create a permutation from a list of disjoint cycles, where each point maps to the next point in its cycle.
+    /// Points not in any cycle are fixed.
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// let f = Permutation::from_cycles(5, &[vec![0, 1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!(f.as_vec(), &[1, 2, 0, 4, 3]);
+    /// ```
+    pub fn from_cycles(
+        degree: usize,
+        cycles: &[Vec<usize>],
+    ) -> Result<Self, err::PermutationError> {
+        let mut vals: Vec<usize> = (0..degree).collect();
+        let mut seen = vec![false; degree];
+        for cycle in cycles {
+            for (i, &x) in cycle.iter().enumerate() {
+                // Points must be in range and appear in at most one cycle
+                if x >= degree || std::mem::replace(&mut seen[x], true) {
+                    return Err(err::PermutationError::InvalidCycles {
+                        degree,
+                        cycles: cycles.to_vec(),
+                    });
+                }
+                vals[x] = cycle[(i + 1) % cycle.len()];
+            }
+        }
+        Ok(Permutation::from_vec_unchecked(degree, vals))
+    }
+
+    /// Parse a permutation of the given degree from cycle notation, such as (0 1 2)(3 4).
+    /// Points in a cycle may be separated by whitespace or commas.
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// let f = Permutation::from_cycle_notation(6, "(0 1 2)(3 4)").unwrap();
+    /// assert_eq!(f.as_vec(), &[1, 2, 0, 4, 3, 5]);
+    /// assert!(Permutation::from_cycle_notation(6, "(0 1 2").is_err());
+    /// ```
+    pub fn from_cycle_notation(degree: usize, input: &str) -> Result<Self, err::PermutationError> {
+        let cycles = Self::parse_cycles(input)?;
+        Self::from_cycles(degree, &cycles)
+    }
+
+    /// Parse the cycles from cycle notation, without checking them.
+    fn parse_cycles(input: &str) -> Result<Vec<Vec<usize>>, err::PermutationError> {
+        let invalid = || err::PermutationError::InvalidCycleNotation {
+            input: input.to_string(),
+        };
+        let mut cycles = Vec::new();
+        let mut rest = input.trim();
+        while !rest.is_empty() {
+            // Each cycle is of the form (a b c)
+            let end = rest.find(')').ok_or_else(invalid)?;
+            let cycle = rest[..end].strip_prefix('(').ok_or_else(invalid)?;
+            let cycle = cycle
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|x| !x.is_empty())
+                .map(|x| x.parse::<usize>().map_err(|_| invalid()))
+                .collect::<Result<Vec<_>, _>>()?;
+            cycles.push(cycle);
+            rest = rest[end + 1..].trim_start();
+        }
+        Ok(cycles)
+    }
+
+    /// Create a Permutation given an image. This does not perform the validation.
+    pub(crate) fn from_vec_unchecked(degree: usize, vals: Vec<usize>) -> Self {
+        Self {
+            degree,
+            vals: vals.into(),
+        }
+    }
+
+    /// Return the identity permutation on degree points
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// let id = Permutation::id(5);
+    /// assert!(id.is_id())
+    /// ```
+    pub fn id(degree: usize) -> Self {
+        Self::from_vec_unchecked(degree, (0..degree).collect())
+    }
+
+    /// Return if a permutation is the identity permutation.
+    pub fn is_id(&self) -> bool {
+        self.vals.iter().enumerate().all(|(i, x)| i == *x)
+    }
+
+    /// Return the degree of the permutation
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    /// Apply the permutation to a given point.
+    /// If x is less than the degree of the permutation, then the result of applying the permutation is returned. Otherwise an error is returned.
+    pub fn apply(&self, x: usize) -> Result<usize, err::PermutationError> {
+        if x < self.degree {
+            Ok(self.vals[x])
+        } else {
+            Err(err::PermutationError::InvalidPoint {
+                degree: self.degree,
+                point: x,
+            })
+        }
+    }
+
+    /// Return the inverse permutation.
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// let f = Permutation::from_vec(3, vec![1, 2, 0]).unwrap();
+    /// assert!(f.multiply(&f.inverse()).unwrap().is_id());
+    /// ```
+    pub fn inverse(&self) -> Self {
+        let mut vals = vec![0; self.degree];
+        for (i, &x) in self.vals.iter().enumerate() {
+            vals[x] = i;
+        }
+        Self::from_vec_unchecked(self.degree, vals)
+    }
+
+    /// Return the non-trivial cycles of the permutation.
+    /// Each cycle starts with its smallest point, and the cycles are sorted by their smallest point.
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let mut seen = vec![false; self.degree];
+        let mut cycles = Vec::new();
+        for start in 0..self.degree {
+            if seen[start] || self.vals[start] == start {
+                continue;
+            }
+            let mut cycle = vec![start];
+            seen[start] = true;
+            let mut cur = self.vals[start];
+            while cur != start {
+                cycle.push(cur);
+                seen[cur] = true;
+                cur = self.vals[cur];
+            }
+            cycles.push(cycle);
+        }
+        cycles
+    }
+
+    /// Return the order of the permutation, which is the least common multiple of the cycle lengths.
+    /// Returns `None` if the order does not fit in a `usize`, which can happen for large degrees.
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// let f = Permutation::from_cycles(5, &[vec![0, 1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!(f.order(), Some(6));
+    /// ```
+    pub fn order(&self) -> Option<usize> {
+        fn gcd(a: usize, b: usize) -> usize {
+            if b == 0 {
+                a
+            } else {
+                gcd(b, a % b)
+            }
+        }
+        self.cycles().iter().try_fold(1usize, |acc, cycle| {
+            (acc / gcd(acc, cycle.len())).checked_mul(cycle.len())
+        })
+    }
+
+    /// Return the sign of the permutation, which is 1 if it is even and -1 if it is odd.
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// let f = Permutation::from_cycles(5, &[vec![0, 1, 2], vec![3, 4]]).unwrap();
+    /// assert_eq!(f.sign(), -1);
+    /// ```
+    pub fn sign(&self) -> i8 {
+        // A cycle of length k is a product of k - 1 transpositions.
+        let transpositions: usize = self.cycles().iter().map(|cycle| cycle.len() - 1).sum();
+        if transpositions % 2 == 0 {
+            1
+        } else {
+            -1
+        }
+    }
+
+    /// Compose two permutations.
+    /// This is only defined for permutations of the same degree
+    /// ```
+    /// use yatyat::element::permutation::Permutation;
+    ///
+    /// let f = Permutation::from_vec(3, vec![1, 0, 2]).unwrap();
+    /// let g = Permutation::from_vec(3, vec![0, 2, 1]).unwrap();
+    /// let fg = f.multiply(&g).unwrap();
+    /// assert_eq!(fg.as_vec(), &[2, 0, 1]);
+    /// ```
+    pub fn multiply(&self, other: &Self) -> Result<Self, err::PermutationError> {
+        if self.degree == other.degree {
+            let vals = self.vals.iter().map(|&x| other.vals[x]).collect();
+            Ok(Permutation::from_vec_unchecked(self.degree, vals))
+        } else {
+            Err(err::PermutationError::MismatchingDegree {
+                degree1: self.degree,
+                degree2: other.degree,
+            })
+        }
+    }
+}
+
+impl SemigroupElement for Permutation {
+    fn multiply(&self, other: &Self) -> Self {
+        // Will panic if degrees do not match
+        self.multiply(other).unwrap()
+    }
+
+    fn is_id(&self) -> bool {
+        self.is_id()
+    }
+}
+
+impl From<Permutation> for Transformation {
+    fn from(f: Permutation) -> Self {
+        Transformation::from_vec_unchecked(f.degree, f.vals.to_vec())
+    }
+}
+
+impl TryFrom<Transformation> for Permutation {
+    type Error = err::PermutationError;
+
+    /// Convert a transformation to a permutation, which is an error if it is not a bijection.
+    fn try_from(f: Transformation) -> Result<Self, Self::Error> {
+        Permutation::from_vec(f.degree(), f.as_vec().to_vec())
+    }
+}
+
+/// Parse from cycle notation, taking the degree to be one more than the largest point.
+impl FromStr for Permutation {
+    type Err = err::PermutationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let cycles = Self::parse_cycles(s)?;
+        let degree = cycles.iter().flatten().max().map_or(0, |x| x + 1);
+        Self::from_cycles(degree, &cycles)
+    }
+}
+
+/// Display in cycle notation, omitting fixed points. The identity is displayed as ().
+impl std::fmt::Display for Permutation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cycles = self.cycles();
+        if cycles.is_empty() {
+            return write!(f, "()");
+        }
+        for cycle in cycles {
+            write!(f, "(")?;
+            let mut sep = "";
+            for x in cycle {
+                write!(f, "{}{}", sep, x)?;
+                sep = " ";
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::Permutation;
+    use crate::element::transformation::Transformation;
+
+    #[test]
+    fn id() {
+        let id0 = Permutation::id(0);
+        let id10 = Permutation::id(10);
+        let f = Permutation::from_vec(2, vec![1, 0]).unwrap();
+        assert!(id0.is_id());
+        assert!(id10.is_id());
+        assert!(!f.is_id());
+        assert_eq!(id10.order(), Some(1));
+        assert_eq!(id10.sign(), 1);
+    }
+
+    #[test]
+    fn invalid() {
+        // Invalid for value out of range
+        assert!(Permutation::from_vec(3, vec![0, 1, 4]).is_err());
+        // Invalid for too many values
+        assert!(Permutation::from_vec(4, vec![1, 2, 3]).is_err());
+        // Invalid as not a bijection
+        assert!(Permutation::from_vec(3, vec![1, 1, 0]).is_err());
+        // Invalid as cycles are not disjoint
+        assert!(Permutation::from_cycles(3, &[vec![0, 1], vec![1, 2]]).is_err());
+    }
+
+    #[test]
+    fn multiply_inverse() {
+        let f = Permutation::from_vec(5, vec![3, 2, 4, 0, 1]).unwrap();
+        assert!(f.multiply(&f.inverse()).unwrap().is_id());
+        assert!(f.inverse().multiply(&f).unwrap().is_id());
+        assert!(f.multiply(&Permutation::id(4)).is_err());
+    }
+
+    #[test]
+    fn order_and_sign() {
+        let f = Permutation::from_cycles(7, &[vec![0, 1, 2, 3], vec![4, 5, 6]]).unwrap();
+        assert_eq!(f.order(), Some(12));
+        assert_eq!(f.sign(), -1);
+        let mut g = f.clone();
+        for _ in 1..12 {
+            assert!(!g.is_id());
+            g = g.multiply(&f).unwrap();
+        }
+        assert!(g.is_id());
+        // The product of the primes up to 53 overflows a 64-bit order
+        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+        let mut start = 0;
+        let mut cycles = Vec::new();
+        for &p in primes.iter() {
+            cycles.push((start..start + p).collect());
+            start += p;
+        }
+        let h = Permutation::from_cycles(start, &cycles).unwrap();
+        assert_eq!(h.order(), None);
+    }
+
+    #[test]
+    fn cycle_notation() {
+        let f: Permutation = "(0 1 2)(3 4)".parse().unwrap();
+        assert_eq!(f.degree(), 5);
+        assert_eq!(f.to_string(), "(0 1 2)(3 4)");
+        assert_eq!(f.cycles(), vec![vec![0, 1, 2], vec![3, 4]]);
+        // Cycles are displayed starting from their smallest point
+        let g = Permutation::from_cycle_notation(6, " (4, 3) (2 0 1) ").unwrap();
+        assert_eq!(g.to_string(), "(0 1 2)(3 4)");
+        let id: Permutation = "()".parse().unwrap();
+        assert_eq!(id.to_string(), "()");
+        assert!("(0 1".parse::<Permutation>().is_err());
+        assert!("0 1)".parse::<Permutation>().is_err());
+        assert!("(0 a)".parse::<Permutation>().is_err());
+    }
+
+    #[test]
+    fn transformation_conversion() {
+        let f = Permutation::from_vec(3, vec![1, 2, 0]).unwrap();
+        let t = Transformation::from(f.clone());
+        assert_eq!(t.as_vec(), f.as_vec());
+        assert_eq!(Permutation::try_from(t).unwrap(), f);
+        let t = Transformation::from_vec(3, vec![1, 1, 0]).unwrap();
+        assert!(Permutation::try_from(t).is_err());
+    }
+}
@@ -7,4 +7,6 @@ pub enum TransformationError {
     InvalidPoint { degree: usize, point: usize },
     #[snafu(display("Operation only defined for equal degree : {} != {}", degree1, degree2))]
     MismatchingDegree { degree1: usize, degree2: usize },
+    #[snafu(display("Invalid transformation format: {}", input))]
+    InvalidFormat { input: String },
 }
@@ -0,0 +1,157 @@
+//! Reading and writing transformations in the formats used by GAP and libsemigroups.
+//!
+//! GAP writes transformations as `Transformation([2, 2, 3])` on the points 1..n,
+//! while libsemigroups writes the list of images `{1, 1, 2}` on the points 0..n-1.
+//! Whitespace is ignored when reading either format.
+
+use super::{err::TransformationError, Transformation};
+
+impl Transformation {
+    /// Parse a transformation written in GAP syntax, where the points are 1..n.
+    /// `IdentityTransformation` is read as the identity on zero points.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    ///
+    /// let f = Transformation::from_gap("Transformation( [ 2, 2, 3 ] )").unwrap();
+    /// assert_eq!(f, Transformation::from_vec(3, vec![1, 1, 2]).unwrap());
+    /// assert!(Transformation::from_gap("Transformation([0, 1])").is_err());
+    /// ```
+    pub fn from_gap(input: &str) -> Result<Self, TransformationError> {
+        let stripped = strip_whitespace(input);
+        let (images, rest) = parse_gap_transformation(&stripped).ok_or_else(|| invalid(input))?;
+        if !rest.is_empty() {
+            return Err(invalid(input));
+        }
+        Self::from_gap_images(images)
+    }
+
+    /// Write the transformation in GAP syntax, where the points are 1..n.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    ///
+    /// let f = Transformation::from_vec(3, vec![1, 1, 2]).unwrap();
+    /// assert_eq!(f.to_gap(), "Transformation([2, 2, 3])");
+    /// ```
+    pub fn to_gap(&self) -> String {
+        let images: Vec<usize> = self.as_vec().iter().map(|x| x + 1).collect();
+        format!("Transformation({})", write_list(&images, '[', ']'))
+    }
+
+    /// Parse a transformation written as a libsemigroups style list of images, where the points are 0..n-1.
+    /// The list may be delimited by braces or square brackets.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    ///
+    /// let f = Transformation::from_libsemigroups("{1, 1, 2}").unwrap();
+    /// assert_eq!(f, Transformation::from_libsemigroups("[1, 1, 2]").unwrap());
+    /// assert_eq!(f, Transformation::from_vec(3, vec![1, 1, 2]).unwrap());
+    /// ```
+    pub fn from_libsemigroups(input: &str) -> Result<Self, TransformationError> {
+        let stripped = strip_whitespace(input);
+        let (images, rest) = parse_list(&stripped).ok_or_else(|| invalid(input))?;
+        if !rest.is_empty() {
+            return Err(invalid(input));
+        }
+        Self::from_vec(images.len(), images)
+    }
+
+    /// Write the transformation as a libsemigroups style list of images, where the points are 0..n-1.
+    pub fn to_libsemigroups(&self) -> String {
+        write_list(self.as_vec(), '{', '}')
+    }
+
+    /// Create a transformation from images on the points 1..n.
+    pub(crate) fn from_gap_images(images: Vec<usize>) -> Result<Self, TransformationError> {
+        let degree = images.len();
+        if images.iter().any(|&x| x == 0 || x > degree) {
+            return Err(TransformationError::InvalidImage {
+                degree,
+                image: images,
+            });
+        }
+        Ok(Self::from_vec_unchecked(
+            degree,
+            images.into_iter().map(|x| x - 1).collect(),
+        ))
+    }
+}
+
+/// Return the error for input that could not be parsed.
+pub(crate) fn invalid(input: &str) -> TransformationError {
+    TransformationError::InvalidFormat {
+        input: input.to_string(),
+    }
+}
+
+pub(crate) fn strip_whitespace(input: &str) -> String {
+    input.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// Write a comma separated list between the given delimiters.
+pub(crate) fn write_list(values: &[usize], open: char, close: char) -> String {
+    let values: Vec<String> = values.iter().map(|x| x.to_string()).collect();
+    format!("{}{}{}", open, values.join(", "), close)
+}
+
+/// Parse a list of numbers delimited by braces or square brackets from the start of the input, without whitespace.
+/// Returns the numbers and the remaining input.
+pub(crate) fn parse_list(input: &str) -> Option<(Vec<usize>, &str)> {
+    let close = match input.chars().next()? {
+        '[' => ']',
+        '{' => '}',
+        _ => return None,
+    };
+    let end = input.find(close)?;
+    let inner = &input[1..end];
+    let values = if inner.is_empty() {
+        Vec::new()
+    } else {
+        inner
+            .split(',')
+            .map(|x| x.parse().ok())
+            .collect::<Option<_>>()?
+    };
+    Some((values, &input[end + 1..]))
+}
+
+/// Parse a GAP transformation from the start of the input, without whitespace.
+/// Returns the images on the points 1..n and the remaining input.
+pub(crate) fn parse_gap_transformation(input: &str) -> Option<(Vec<usize>, &str)> {
+    if let Some(rest) = input.strip_prefix("IdentityTransformation") {
+        return Some((Vec::new(), rest));
+    }
+    let (images, rest) = parse_list(input.strip_prefix("Transformation(")?)?;
+    Some((images, rest.strip_prefix(')')?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Transformation;
+
+    #[test]
+    fn round_trip() {
+        let f = Transformation::from_vec(5, vec![4, 0, 0, 3, 2]).unwrap();
+        assert_eq!(Transformation::from_gap(&f.to_gap()).unwrap(), f);
+        assert_eq!(
+            Transformation::from_libsemigroups(&f.to_libsemigroups()).unwrap(),
+            f
+        );
+        assert_eq!(f.to_libsemigroups(), "{4, 0, 0, 3, 2}");
+        assert_eq!(Transformation::id(0).to_gap(), "Transformation([])");
+        assert_eq!(
+            Transformation::from_gap("IdentityTransformation").unwrap(),
+            Transformation::id(0)
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert!(Transformation::from_gap("Transformation([2, 3])").is_err());
+        assert!(Transformation::from_gap("Transformation([1, 2]").is_err());
+        assert!(Transformation::from_gap("Transformation([1, 2])x").is_err());
+        assert!(Transformation::from_gap("Transformation([1, -2])").is_err());
+        assert!(Transformation::from_libsemigroups("{1, 2}").is_err());
+        assert!(Transformation::from_libsemigroups("{0, 1]").is_err());
+        assert!(Transformation::from_libsemigroups("(0, 1)").is_err());
+    }
+}
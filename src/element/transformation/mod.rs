@@ -3,6 +3,7 @@ use std::sync::Arc;
 use super::SemigroupElement;
 
 pub mod err;
+pub mod format;
 
 /// Representation of a transformation on the points 0..n-1
 /// This is stored as a vector using the images of each point from 0..n-1
@@ -141,6 +142,28 @@ impl std::fmt::Display for Transformation {
     }
 }
 
+/// Transformations are serialized as their list of images.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Transformation {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.as_vec().serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Transformation {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let vals = Vec::<usize>::deserialize(deserializer)?;
+        Self::from_vec(vals.len(), vals).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::Transformation;
@@ -180,4 +203,14 @@ mod tests {
         let fg = Transformation::from_vec(4, vec![1, 1, 3, 1]).unwrap();
         assert_eq!(fg, f.multiply(&g).unwrap());
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let f = Transformation::from_vec(4, vec![2, 2, 3, 1]).unwrap();
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, "[2,2,3,1]");
+        assert_eq!(serde_json::from_str::<Transformation>(&json).unwrap(), f);
+        assert!(serde_json::from_str::<Transformation>("[2,2,3,4]").is_err());
+    }
 }
@@ -0,0 +1,374 @@
+//! Command line tool to enumerate a transformation semigroup and report on its structure.
+//! Only transformations can be read, not the other kinds of element in the library.
+//!
+//! The generators are read from a file, or stdin if no file is given, and can be written as a GAP semigroup or monoid,
+//! a libsemigroups style list of lists, or one transformation per line in either format.
+
+use std::{
+    fmt::Write as _,
+    io::{self, Read},
+    process,
+};
+
+use yatyat::{
+    element::transformation::Transformation,
+    semigroup::{
+        algs::froidure_pin::{
+            froidure_pin_impl::FroidurePin, green::Classes, parallel::FroidurePinParallel,
+            simple::FroidurePinSimple, EnumeratedSemigroup, FroidurePinBuilder, FroidurePinResult,
+        },
+        impls::transformation::TransformationSemigroup,
+    },
+};
+
+const USAGE: &str = "Usage: yatyat [OPTIONS] [FILE]
+
+Enumerate the transformation semigroup generated by the transformations in FILE, or stdin if no FILE is given.
+Only transformations can be read.
+The generators can be a GAP semigroup or monoid, a libsemigroups style list of lists,
+or one transformation per line in either format. Anything after a # on a line is ignored.
+
+Options:
+    --simple            Use the simple version of Froidure-Pin
+    --parallel          Use the multithreaded version of Froidure-Pin
+    --size              Print the number of elements (the default if nothing else is chosen)
+    --rules             Print the number of rewrite rules
+    --idempotents       Print the number of idempotents
+    --green             Print a summary of the D-classes
+    --elements          Print every element, in short-lex order
+    --format FORMAT     Format for elements: plain, gap or libsemigroups (default plain)
+    -h, --help          Print this message";
+
+/// Which implementation of Froidure-Pin to use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Algorithm {
+    Standard,
+    Simple,
+    Parallel,
+}
+
+/// How to print the elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Plain,
+    Gap,
+    Libsemigroups,
+}
+
+impl Format {
+    fn write(self, f: &Transformation) -> String {
+        match self {
+            Format::Plain => f.to_string(),
+            Format::Gap => f.to_gap(),
+            Format::Libsemigroups => f.to_libsemigroups(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    algorithm: Algorithm,
+    format: Format,
+    size: bool,
+    rules: bool,
+    idempotents: bool,
+    green: bool,
+    elements: bool,
+    file: Option<String>,
+}
+
+/// Parse the arguments, not including the program name.
+/// None is returned if the help message was asked for.
+fn parse_args<I>(args: I) -> Result<Option<Options>, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut options = Options {
+        algorithm: Algorithm::Standard,
+        format: Format::Plain,
+        size: false,
+        rules: false,
+        idempotents: false,
+        green: false,
+        elements: false,
+        file: None,
+    };
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--simple" => options.algorithm = Algorithm::Simple,
+            "--parallel" => options.algorithm = Algorithm::Parallel,
+            "--size" => options.size = true,
+            "--rules" => options.rules = true,
+            "--idempotents" => options.idempotents = true,
+            "--green" => options.green = true,
+            "--elements" => options.elements = true,
+            "--format" => {
+                options.format = match args.next().as_deref() {
+                    Some("plain") => Format::Plain,
+                    Some("gap") => Format::Gap,
+                    Some("libsemigroups") => Format::Libsemigroups,
+                    Some(other) => return Err(format!("Unknown format: {}", other)),
+                    None => return Err("Missing value for --format".to_string()),
+                }
+            }
+            flag if flag.starts_with('-') => return Err(format!("Unknown option: {}", flag)),
+            file => {
+                if options.file.replace(file.to_string()).is_some() {
+                    return Err("Only one input file can be given".to_string());
+                }
+            }
+        }
+    }
+    if !(options.rules || options.idempotents || options.green || options.elements) {
+        options.size = true;
+    }
+    Ok(Some(options))
+}
+
+/// Read the generators, detecting which format they are written in.
+fn parse_semigroup(input: &str) -> Result<TransformationSemigroup, String> {
+    // Drop comments, surrounding whitespace, blank lines and any byte order mark before detecting the format.
+    let lines: Vec<&str> = input
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(|line| line.split('#').next().unwrap().trim())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let text = lines.join("\n");
+    // Whitespace is ignored by every format, so it is removed to detect one, e.g. in `{ {1, 0}, {0, 0} }`.
+    let compact: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+    let result = if compact.starts_with("Semigroup") || compact.starts_with("Monoid") {
+        TransformationSemigroup::from_gap(&text)
+    } else if compact.starts_with("{{") || compact.starts_with("[[") {
+        TransformationSemigroup::from_libsemigroups(&text)
+    } else {
+        // Otherwise there is one transformation per line.
+        let gens = lines
+            .iter()
+            .map(|line| {
+                let is_gap = line.contains("Transformation");
+                let f = if is_gap {
+                    Transformation::from_gap(line)
+                } else {
+                    Transformation::from_libsemigroups(line)
+                };
+                f.map(|f| (f, is_gap))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        // As in a GAP semigroup, transformations in GAP syntax are extended to the largest degree by fixing the extra points.
+        let degree = gens.iter().map(|(f, _)| f.degree()).max().unwrap_or(0);
+        let gens = gens
+            .into_iter()
+            .map(|(f, is_gap)| {
+                if is_gap && f.degree() < degree {
+                    let images = f
+                        .as_vec()
+                        .iter()
+                        .copied()
+                        .chain(f.degree()..degree)
+                        .collect();
+                    Transformation::from_vec(degree, images)
+                } else {
+                    Ok(f)
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| e.to_string())?;
+        TransformationSemigroup::new(&gens)
+    };
+    result.map_err(|e| e.to_string())
+}
+
+/// Produce the requested report for the enumerated semigroup.
+fn report(res: &FroidurePinResult<Transformation>, options: &Options) -> String {
+    let mut out = String::new();
+    if options.size {
+        writeln!(out, "size: {}", res.size()).unwrap();
+    }
+    if options.rules {
+        writeln!(out, "rules: {}", res.rules().len()).unwrap();
+    }
+    if options.idempotents {
+        writeln!(out, "idempotents: {}", res.number_of_idempotents()).unwrap();
+    }
+    if options.green {
+        let green = res.greens_relations();
+        let d_classes = green.d_classes();
+        writeln!(out, "D-classes: {}", d_classes.number_of_classes()).unwrap();
+        for (i, class) in d_classes.classes().iter().enumerate() {
+            let rep = class[0];
+            let count = |classes: &Classes| {
+                let mut indices: Vec<_> = class
+                    .iter()
+                    .filter_map(|&x| classes.class_index(x))
+                    .collect();
+                indices.sort_unstable();
+                indices.dedup();
+                indices.len()
+            };
+            writeln!(
+                out,
+                "D-class {}: size {}, R-classes {}, L-classes {}, H-class size {}, representative {}",
+                i,
+                class.len(),
+                count(green.r_classes()),
+                count(green.l_classes()),
+                green.h_classes().class_of(rep).map_or(0, |h| h.len()),
+                options.format.write(res.element(rep).unwrap()),
+            )
+            .unwrap();
+        }
+    }
+    if options.elements {
+        for x in res.iter() {
+            writeln!(out, "{}", options.format.write(x)).unwrap();
+        }
+    }
+    out
+}
+
+fn run(options: &Options) -> Result<String, String> {
+    let input = match options.file.as_ref() {
+        Some(file) => {
+            std::fs::read_to_string(file).map_err(|e| format!("Could not read {}: {}", file, e))?
+        }
+        None => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|e| format!("Could not read stdin: {}", e))?;
+            input
+        }
+    };
+    let s = parse_semigroup(&input)?;
+    let res = match options.algorithm {
+        Algorithm::Standard => FroidurePin::new(&s).build(),
+        Algorithm::Simple => FroidurePinSimple::new(&s).build(),
+        Algorithm::Parallel => FroidurePinParallel::new(&s).build(),
+    };
+    Ok(report(&res, options))
+}
+
+fn main() {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            process::exit(2);
+        }
+    };
+    match run(&options) {
+        Ok(out) => print!("{}", out),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Result<Option<Options>, String> {
+        parse_args(args.iter().map(|x| x.to_string()))
+    }
+
+    #[test]
+    fn arguments() {
+        let options = args(&["--simple", "--format", "gap", "gens.txt"])
+            .unwrap()
+            .unwrap();
+        assert_eq!(options.algorithm, Algorithm::Simple);
+        assert_eq!(options.format, Format::Gap);
+        assert_eq!(options.file.as_deref(), Some("gens.txt"));
+        // Size is printed when nothing else is asked for.
+        assert!(options.size);
+        assert!(!args(&["--green"]).unwrap().unwrap().size);
+        assert_eq!(args(&["--help"]).unwrap(), None);
+        assert!(args(&["--format"]).is_err());
+        assert!(args(&["--format", "json"]).is_err());
+        assert!(args(&["--unknown"]).is_err());
+        assert!(args(&["a", "b"]).is_err());
+    }
+
+    #[test]
+    fn input_formats() {
+        let gap = parse_semigroup("Monoid([Transformation([2, 1, 3]), Transformation([2, 3, 1])])");
+        let lists = parse_semigroup("{{1, 0, 2}, {1, 2, 0}}");
+        let lines = parse_semigroup("# S3\nTransformation([2, 1, 3])\n\n{1, 2, 0}\n");
+        for s in [gap, lists, lines] {
+            let res = FroidurePin::new(&s.unwrap()).build();
+            assert_eq!(res.size(), 6);
+        }
+        // Leading blank lines, spaces, comments and a byte order mark do not change the format detected.
+        let padded = [
+            "\n\n   Monoid([Transformation([2, 1, 3]),\n  Transformation([2, 3, 1])])\n",
+            "  \r\n\t{{1, 0, 2},\r\n {1, 2, 0}}",
+            "# S3\n# as a monoid\nMonoid([Transformation([2, 1, 3]), Transformation([2, 3, 1])])",
+            "\u{feff}[[1, 0, 2], [1, 2, 0]]",
+            "{ {1, 0, 2}, {1, 2, 0} }",
+            "[\n  [1, 0, 2],\n  [1, 2, 0]\n]",
+            "Monoid ([Transformation([2, 1, 3]), Transformation([2, 3, 1])])",
+        ];
+        for input in padded.iter() {
+            let res = FroidurePin::new(&parse_semigroup(input).unwrap()).build();
+            assert_eq!(res.size(), 6);
+        }
+        assert!(parse_semigroup("{1, 2}").is_err());
+    }
+
+    #[test]
+    fn one_transformation_per_line() {
+        // Transformations in GAP syntax are extended to the largest degree, as in a GAP semigroup.
+        let s = parse_semigroup("Transformation([2, 1])\nTransformation([1, 1, 1])").unwrap();
+        assert_eq!(s.degree(), 3);
+        assert_eq!(FroidurePin::new(&s).build().size(), 4);
+        let mixed = parse_semigroup("Transformation([2, 1])\n{0, 0, 2}").unwrap();
+        assert_eq!(FroidurePin::new(&mixed).build().size(), 4);
+        // Lists of images must have the same degree, as in libsemigroups.
+        assert!(parse_semigroup("{1, 0}\n{0, 0, 0}").is_err());
+        // Comments can also follow a transformation on the same line.
+        let commented = parse_semigroup(
+            "Transformation([2, 1, 3]) # a transposition\n{1, 2, 0}   # a 3-cycle\n# done",
+        )
+        .unwrap();
+        assert_eq!(FroidurePin::new(&commented).build().size(), 6);
+        let gap = parse_semigroup(
+            "Monoid([Transformation([2, 1, 3]), # the generators\n Transformation([2, 3, 1])])",
+        );
+        assert_eq!(FroidurePin::new(&gap.unwrap()).build().size(), 6);
+    }
+
+    #[test]
+    fn report_output() {
+        let s = parse_semigroup("{0, 0}\n{1, 0}").unwrap();
+        let res = FroidurePin::new(&s).build();
+        let options = args(&[
+            "--size",
+            "--idempotents",
+            "--green",
+            "--elements",
+            "--format",
+            "libsemigroups",
+        ])
+        .unwrap()
+        .unwrap();
+        assert_eq!(
+            report(&res, &options),
+            "size: 4\n\
+             idempotents: 3\n\
+             D-classes: 2\n\
+             D-class 0: size 2, R-classes 1, L-classes 1, H-class size 2, representative {0, 1}\n\
+             D-class 1: size 2, R-classes 1, L-classes 2, H-class size 1, representative {0, 0}\n\
+             {0, 1}\n{0, 0}\n{1, 0}\n{1, 1}\n"
+        );
+    }
+}
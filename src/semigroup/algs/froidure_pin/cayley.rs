@@ -0,0 +1,228 @@
+use std::{
+    fmt::{Display, Write},
+    hash::Hash,
+};
+
+use itertools::Itertools;
+
+use super::{EnumeratedSemigroup, FroidurePinResult, Side};
+use crate::element::SemigroupElement;
+
+/// Colours given to the edges of each generator in DOT output, which are reused if there are more generators.
+const COLOURS: [&str; 8] = [
+    "red",
+    "blue",
+    "forestgreen",
+    "darkorange",
+    "purple",
+    "brown",
+    "deeppink",
+    "gray40",
+];
+
+/// Export of the left or right Cayley graph of an enumerated semigroup.
+///
+/// Nodes are the positions of the elements, and there is an edge labelled i from x to y
+/// if y is x multiplied by generator i on the given side.
+#[derive(Debug)]
+pub struct CayleyGraph<'a, S> {
+    result: &'a S,
+    side: Side,
+    colours: bool,
+}
+
+// Implemented by hand so the semigroup itself does not need to be cloned.
+impl<'a, S> Clone for CayleyGraph<'a, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, S> Copy for CayleyGraph<'a, S> {}
+
+impl<'a, S> CayleyGraph<'a, S>
+where
+    S: EnumeratedSemigroup,
+{
+    /// Create the export of the Cayley graph on the given side. Edges are coloured by generator by default.
+    pub fn new(result: &'a S, side: Side) -> Self {
+        Self {
+            result,
+            side,
+            colours: true,
+        }
+    }
+
+    /// Set whether edges in DOT output are coloured by generator.
+    pub fn with_colours(mut self, colours: bool) -> Self {
+        self.colours = colours;
+        self
+    }
+
+    /// Return which side the generators multiply on.
+    pub fn side(&self) -> Side {
+        self.side
+    }
+
+    /// Return the edges of the graph as (source, generator, target), ordered by source and then generator.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, usize)> + 'a {
+        let graph = *self;
+        (0..graph.result.size()).flat_map(move |x| {
+            (0..graph.result.number_of_generators())
+                .filter_map(move |i| Some((x, i, graph.target(x, i)?)))
+        })
+    }
+
+    /// Write the graph in Graphviz DOT format, where each node is labelled by the normal form of its element.
+    /// The letters of the normal form are separated by spaces, so that labels are unambiguous with ten or more generators.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    /// use yatyat::semigroup::algs::froidure_pin::{
+    ///     froidure_pin_impl::FroidurePin, EnumeratedSemigroup, FroidurePinBuilder, Side,
+    /// };
+    /// use yatyat::semigroup::impls::transformation::TransformationSemigroup;
+    ///
+    /// let s = TransformationSemigroup::new(&[Transformation::from_vec(2, vec![1, 0]).unwrap()]).unwrap();
+    /// let res = FroidurePin::new(&s).build();
+    /// let dot = res.cayley_graph(Side::Right).with_colours(false).to_dot();
+    /// assert_eq!(
+    ///     dot,
+    ///     "digraph {\n  0 [label=\"ε\"];\n  1 [label=\"0\"];\n  0 -> 1 [label=\"0\"];\n  1 -> 0 [label=\"0\"];\n}\n"
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        self.write_dot(|x| {
+            let word = self.result.normal_form(x).unwrap();
+            if word.is_empty_word() {
+                "ε".to_string()
+            } else {
+                word.as_vec().iter().join(" ")
+            }
+        })
+    }
+
+    /// Write the graph as an adjacency list, with one line per element listing its targets by generator.
+    /// Missing edges are written as `-`.
+    pub fn to_adjacency_list(&self) -> String {
+        let mut out = String::new();
+        for x in 0..self.result.size() {
+            let targets: Vec<String> = (0..self.result.number_of_generators())
+                .map(|i| self.target(x, i).map_or("-".to_string(), |y| y.to_string()))
+                .collect();
+            writeln!(out, "{}: {}", x, targets.join(" ")).unwrap();
+        }
+        out
+    }
+
+    /// Write the edges as CSV with the header `source,generator,target`.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("source,generator,target\n");
+        for (x, i, y) in self.edges() {
+            writeln!(out, "{},{},{}", x, i, y).unwrap();
+        }
+        out
+    }
+
+    fn target(&self, element: usize, generator: usize) -> Option<usize> {
+        self.result.cayley(self.side, element, generator)
+    }
+
+    fn write_dot<F>(&self, label: F) -> String
+    where
+        F: Fn(usize) -> String,
+    {
+        let mut out = String::from("digraph {\n");
+        for x in 0..self.result.size() {
+            writeln!(out, "  {} [label=\"{}\"];", x, escape(&label(x))).unwrap();
+        }
+        for (x, i, y) in self.edges() {
+            if self.colours {
+                let colour = COLOURS[i % COLOURS.len()];
+                writeln!(
+                    out,
+                    "  {} -> {} [label=\"{}\", color=\"{}\", fontcolor=\"{}\"];",
+                    x, y, i, colour, colour
+                )
+                .unwrap();
+            } else {
+                writeln!(out, "  {} -> {} [label=\"{}\"];", x, y, i).unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl<'a, U> CayleyGraph<'a, FroidurePinResult<U>>
+where
+    U: SemigroupElement + Hash + Display,
+{
+    /// Write the graph in Graphviz DOT format, where each node is labelled by its element.
+    pub fn to_labelled_dot(&self) -> String {
+        self.write_dot(|x| self.result.elements[x].to_string())
+    }
+}
+
+/// Escape a label so it can be written in a quoted DOT string.
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Side;
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{
+                froidure_pin_impl::FroidurePin, EnumeratedSemigroup, FroidurePinBuilder,
+            },
+            impls::transformation::TransformationSemigroup,
+        },
+    };
+
+    #[test]
+    fn exports() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(2, vec![0, 0]).unwrap(),
+            Transformation::from_vec(2, vec![1, 0]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        assert_eq!(res.size(), 4);
+        let right = res.cayley_graph(Side::Right);
+        let left = res.cayley_graph(Side::Left);
+        assert_eq!(right.side(), Side::Right);
+        for (x, i, y) in right.edges() {
+            assert_eq!(res.right_cayley(x, i), Some(y));
+        }
+        assert_eq!(left.edges().count(), 4 * 2);
+        assert_eq!(
+            right.to_adjacency_list(),
+            "0: 1 2\n1: 1 3\n2: 1 0\n3: 1 1\n"
+        );
+        let csv = left.to_csv();
+        assert!(csv.starts_with("source,generator,target\n"));
+        assert_eq!(csv.lines().count(), 1 + 8);
+        let dot = right.to_labelled_dot();
+        assert!(dot.contains("  0 [label=\"(0:0, 1:1)\"];"));
+        assert!(dot.contains("  0 -> 1 [label=\"0\", color=\"red\", fontcolor=\"red\"];"));
+        assert!(dot.contains("  0 -> 2 [label=\"1\", color=\"blue\", fontcolor=\"blue\"];"));
+        let dot = right.to_dot();
+        assert!(dot.contains("  3 [label=\"0 1\"];"));
+    }
+
+    #[test]
+    fn many_generators() {
+        // Ten constant maps and a cycle, so that the square of generator 10 is a new element.
+        let mut generators: Vec<_> = (0..10)
+            .map(|i| Transformation::from_vec(12, vec![i; 12]).unwrap())
+            .collect();
+        generators.push(Transformation::from_vec(12, (1..12).chain(0..1).collect()).unwrap());
+        let s = TransformationSemigroup::new(&generators).unwrap();
+        let res = FroidurePin::new(&s).build();
+        let dot = res.cayley_graph(Side::Right).to_dot();
+        assert!(dot.contains("[label=\"10 10\"];"));
+        assert!(!dot.contains("[label=\"1010\"];"));
+    }
+}
@@ -0,0 +1,400 @@
+use super::{
+    cayley_lookup, green::Classes, prefix_normal_form, CayleyGraphType, EnumeratedSemigroup, Side,
+};
+use crate::{
+    semigroup::word::Word,
+    utils::{union_find::UnionFind, vec2::Vec2},
+    DetHashMap,
+};
+
+/// Which side a congruence is compatible with multiplication on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CongruenceKind {
+    /// If x ~ y then xs ~ ys.
+    Right,
+    /// If x ~ y then sx ~ sy.
+    Left,
+    /// If x ~ y then xs ~ ys and sx ~ sy.
+    TwoSided,
+}
+
+/// A right, left or two-sided congruence of a semigroup, given by its classes.
+/// Elements are given by their position in the semigroup the congruence was computed from,
+/// and classes are numbered in order of their smallest element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Congruence {
+    kind: CongruenceKind,
+    classes: Classes,
+}
+
+impl Congruence {
+    /// Compute the least congruence of the given kind containing the pairs of positions,
+    /// or return None if any of the positions are out of range.
+    ///
+    /// This merges the classes of each pair, and then the classes of their products with each generator,
+    /// on the sides given by the kind of congruence, until nothing changes.
+    pub fn new<S>(result: &S, kind: CongruenceKind, pairs: &[(usize, usize)]) -> Option<Self>
+    where
+        S: EnumeratedSemigroup,
+    {
+        let size = result.size();
+        if pairs.iter().any(|&(x, y)| x >= size || y >= size) {
+            return None;
+        }
+        let sides: &[Side] = match kind {
+            CongruenceKind::Right => &[Side::Right],
+            CongruenceKind::Left => &[Side::Left],
+            CongruenceKind::TwoSided => &[Side::Right, Side::Left],
+        };
+        let mut forest = UnionFind::new(size);
+        let mut pending = pairs.to_vec();
+        while let Some((x, y)) = pending.pop() {
+            if !forest.union(x, y) {
+                continue;
+            }
+            for &side in sides.iter() {
+                // The Cayley graphs are complete once the enumeration has finished.
+                pending.extend((0..result.number_of_generators()).map(|a| {
+                    let (xa, ya) = (result.cayley(side, x, a), result.cayley(side, y, a));
+                    (xa.unwrap(), ya.unwrap())
+                }));
+            }
+        }
+        Some(Self::from_forest(kind, &mut forest))
+    }
+
+    /// Create the congruence from a union-find forest, numbering the classes in order of their smallest element.
+    fn from_forest(kind: CongruenceKind, forest: &mut UnionFind) -> Self {
+        let mut numbering = vec![None; forest.n_points()];
+        let mut next = 0;
+        let class_index = (0..forest.n_points())
+            .map(|x| {
+                let root = forest.find(x);
+                *numbering[root].get_or_insert_with(|| {
+                    next += 1;
+                    next - 1
+                })
+            })
+            .collect();
+        Self {
+            kind,
+            classes: Classes::from_class_index(class_index),
+        }
+    }
+
+    /// Return the kind of congruence.
+    pub fn kind(&self) -> CongruenceKind {
+        self.kind
+    }
+
+    /// Return the classes of the congruence.
+    pub fn classes(&self) -> &Classes {
+        &self.classes
+    }
+
+    /// Return the number of classes.
+    pub fn number_of_classes(&self) -> usize {
+        self.classes.number_of_classes()
+    }
+
+    /// Return if the elements at the given positions are in the same class.
+    /// False is returned if either position is out of range.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        match (self.classes.class_index(x), self.classes.class_index(y)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// Return if every pair in this congruence is also in the other congruence.
+    /// False is returned if the congruences are of different kinds or on semigroups of different sizes.
+    pub fn is_contained_in(&self, other: &Self) -> bool {
+        self.is_comparable(other)
+            && self.classes.classes().iter().all(|class| {
+                let index = other.classes.class_index(class[0]);
+                class.iter().all(|&x| other.classes.class_index(x) == index)
+            })
+    }
+
+    /// Return the least congruence containing both congruences, or None if they are of different kinds
+    /// or on semigroups of different sizes.
+    pub fn join(&self, other: &Self) -> Option<Self> {
+        if !self.is_comparable(other) {
+            return None;
+        }
+        // The equivalence generated by two congruences of the same kind is already a congruence of that kind.
+        let mut forest = UnionFind::new(self.size());
+        for class in self.classes.classes().iter().chain(other.classes.classes()) {
+            for &x in class.iter() {
+                forest.union(x, class[0]);
+            }
+        }
+        Some(Self::from_forest(self.kind, &mut forest))
+    }
+
+    /// Return the greatest congruence contained in both congruences, which is their intersection.
+    /// None is returned if they are of different kinds or on semigroups of different sizes.
+    pub fn meet(&self, other: &Self) -> Option<Self> {
+        if !self.is_comparable(other) {
+            return None;
+        }
+        let mut numbering = DetHashMap::default();
+        let class_index = (0..self.size())
+            .map(|x| {
+                let pair = (self.classes.class_index(x), other.classes.class_index(x));
+                let next = numbering.len();
+                *numbering.entry(pair).or_insert(next)
+            })
+            .collect();
+        Some(Self {
+            kind: self.kind,
+            classes: Classes::from_class_index(class_index),
+        })
+    }
+
+    /// Return the number of elements of the semigroup.
+    fn size(&self) -> usize {
+        self.classes.number_of_elements()
+    }
+
+    fn is_comparable(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.size() == other.size()
+    }
+
+    /// Return the quotient of the semigroup by the congruence, where the elements are the classes.
+    /// The quotient is only a semigroup for two-sided congruences, so None is returned otherwise.
+    /// None is also returned if the congruence is on a semigroup of a different size.
+    pub fn quotient<S>(&self, result: &S) -> Option<Quotient>
+    where
+        S: EnumeratedSemigroup,
+    {
+        if self.kind != CongruenceKind::TwoSided || self.size() != result.size() {
+            return None;
+        }
+        let n_classes = self.number_of_classes();
+        let n_gens = result.number_of_generators();
+        let class = |x: usize| self.classes.class_index(x).unwrap();
+        let representatives = self.classes.representatives();
+        // Products of any element of a class are in the same class, so we use the smallest.
+        let graph = |side: Side| {
+            let mut graph = Vec2::new(n_classes, n_gens);
+            for (c, &rep) in representatives.iter().enumerate() {
+                for a in 0..n_gens {
+                    graph[(c, a)] = result.cayley(side, rep, a).map(class);
+                }
+            }
+            graph
+        };
+        // Elements are in short-lex order of their normal forms, so the smallest element of a class has the least normal form,
+        // and removing its last letter gives the normal form of another class.
+        let prefix = representatives
+            .iter()
+            .map(|&rep| {
+                let word = result.normal_form(rep).unwrap();
+                let (&last, rest) = word.as_vec().split_last()?;
+                let previous = result.word_to_position(&rest.iter().copied().collect());
+                Some((previous.map(class), last))
+            })
+            .collect();
+        Some(Quotient {
+            is_monoid: result.is_monoid(),
+            right_cayley_graph: graph(Side::Right),
+            left_cayley_graph: graph(Side::Left),
+            prefix,
+            generators: (0..n_gens)
+                .map(|a| class(result.generator_position(a).unwrap()))
+                .collect(),
+        })
+    }
+}
+
+/// A quotient of an enumerated semigroup, where the elements are the classes of a congruence,
+/// numbered in order of their smallest element.
+/// If the semigroup was a monoid, the identity is at position zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quotient {
+    is_monoid: bool,
+    // The left and right Cayley graphs, which index into the elements.
+    right_cayley_graph: CayleyGraphType,
+    left_cayley_graph: CayleyGraphType,
+    // The normal form of each element is the normal form of the element at the first position followed by the generator,
+    // where None is the empty word. The identity of a monoid has no prefix.
+    prefix: Vec<Option<(Option<usize>, usize)>>,
+    // The position of each generator
+    generators: Vec<usize>,
+}
+
+impl EnumeratedSemigroup for Quotient {
+    fn size(&self) -> usize {
+        self.right_cayley_graph.n_rows()
+    }
+
+    fn is_monoid(&self) -> bool {
+        self.is_monoid
+    }
+
+    fn number_of_generators(&self) -> usize {
+        self.generators.len()
+    }
+
+    fn generator_position(&self, generator: usize) -> Option<usize> {
+        self.generators.get(generator).copied()
+    }
+
+    fn right_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        cayley_lookup(&self.right_cayley_graph, element, generator)
+    }
+
+    fn left_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        cayley_lookup(&self.left_cayley_graph, element, generator)
+    }
+
+    fn normal_form(&self, index: usize) -> Option<Word<usize>> {
+        prefix_normal_form(&self.prefix, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Congruence, CongruenceKind};
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{
+                froidure_pin_impl::FroidurePin,
+                ideal::{Ideal, IdealKind},
+                lattice::CongruenceLattice,
+                EnumeratedSemigroup, FroidurePinBuilder, Side,
+            },
+            impls::transformation::TransformationSemigroup,
+            word::Word,
+        },
+    };
+
+    #[test]
+    fn generated_congruences() {
+        // The full transformation monoid of degree 3.
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let pairs = [(
+            Transformation::from_vec(3, vec![0, 0, 0]).unwrap(),
+            Transformation::from_vec(3, vec![1, 1, 1]).unwrap(),
+        )];
+        // Identifying two constants as a two-sided congruence identifies all of them, giving the Rees quotient by rank one.
+        let two_sided = res.congruence(CongruenceKind::TwoSided, &pairs).unwrap();
+        assert_eq!(two_sided.kind(), CongruenceKind::TwoSided);
+        assert_eq!(two_sided.number_of_classes(), 27 - 2);
+        // Constants are right zeros, so left multiplication does nothing new but right multiplication identifies all of them.
+        let right = res.congruence(CongruenceKind::Right, &pairs).unwrap();
+        assert_eq!(right.number_of_classes(), 27 - 2);
+        let left = res.congruence(CongruenceKind::Left, &pairs).unwrap();
+        assert_eq!(left.number_of_classes(), 27 - 1);
+        assert!(right.quotient(&res).is_none());
+        assert!(Congruence::new(&res, CongruenceKind::Right, &[(0, 27)]).is_none());
+        // The classes of each congruence are closed under multiplication on the correct side.
+        for congruence in [&two_sided, &right, &left] {
+            for x in 0..res.size() {
+                for y in 0..res.size() {
+                    if !congruence.contains(x, y) {
+                        continue;
+                    }
+                    for a in 0..3 {
+                        if congruence.kind() != CongruenceKind::Left {
+                            let (xa, ya) = (res.right_cayley(x, a), res.right_cayley(y, a));
+                            assert!(congruence.contains(xa.unwrap(), ya.unwrap()));
+                        }
+                        if congruence.kind() != CongruenceKind::Right {
+                            let (ax, ay) = (res.left_cayley(x, a), res.left_cayley(y, a));
+                            assert!(congruence.contains(ax.unwrap(), ay.unwrap()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quotient() {
+        // Identifying the transposition with the identity in the symmetric group of degree 3 gives the trivial group,
+        // while identifying the 3-cycle with the identity gives the cyclic group of order 2.
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let trivial = Congruence::new(&res, CongruenceKind::TwoSided, &[(0, 1)])
+            .unwrap()
+            .quotient(&res)
+            .unwrap();
+        assert_eq!(trivial.size(), 1);
+        let c2 = Congruence::new(&res, CongruenceKind::TwoSided, &[(0, 2)])
+            .unwrap()
+            .quotient(&res)
+            .unwrap();
+        assert_eq!(c2.size(), 2);
+        assert!(c2.is_monoid());
+        assert_eq!(c2.number_of_generators(), 2);
+        assert_eq!(c2.generator_position(0), Some(1));
+        assert_eq!(c2.generator_position(1), Some(0));
+        let word = |w: &[usize]| w.iter().copied().collect::<Word<usize>>();
+        assert_eq!(c2.word_to_position(&word(&[0, 1, 0])), Some(0));
+        assert_eq!(c2.word_to_position(&word(&[0, 1, 1])), Some(1));
+        assert_eq!(c2.left_cayley(1, 0), Some(0));
+        assert_eq!(c2.right_cayley(1, 2), None);
+    }
+
+    #[test]
+    fn quotient_algorithms() {
+        // The Rees quotient of the full transformation monoid of degree 3 by the constant transformations.
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let rees = res
+            .congruence(
+                CongruenceKind::TwoSided,
+                &[(
+                    Transformation::from_vec(3, vec![0, 0, 0]).unwrap(),
+                    Transformation::from_vec(3, vec![1, 1, 1]).unwrap(),
+                )],
+            )
+            .unwrap();
+        let quotient = rees.quotient(&res).unwrap();
+        assert_eq!(quotient.size(), 25);
+        // Normal forms are in short-lex order and evaluate to their element.
+        let normal_forms = quotient.normal_forms();
+        assert!(normal_forms.windows(2).all(|w| w[0] < w[1]));
+        for (x, word) in normal_forms.iter().enumerate() {
+            assert_eq!(quotient.word_to_position(word), Some(x));
+        }
+        // The J-classes of rank 3 and 2 are kept, and the constants become the zero.
+        let zero = quotient.zero().unwrap();
+        let green = quotient.greens_relations();
+        assert_eq!(green.j_classes().number_of_classes(), 3);
+        assert_eq!(quotient.minimal_ideal().unwrap().elements(), &[zero]);
+        let ideal = Ideal::new(&quotient, IdealKind::TwoSided, &[quotient.size() - 1]).unwrap();
+        assert_eq!(ideal.size(), 18 + 1);
+        assert_eq!(ideal.rees_quotient(&quotient).unwrap().size(), 25 - 18);
+        // Congruences of the quotient correspond to the congruences containing the Rees congruence.
+        let lattice = CongruenceLattice::new(&quotient, CongruenceKind::TwoSided);
+        let above = res
+            .congruence_lattice(CongruenceKind::TwoSided)
+            .congruences()
+            .iter()
+            .filter(|c| rees.is_contained_in(c))
+            .count();
+        assert_eq!(lattice.number_of_congruences(), above);
+        let dot = quotient.cayley_graph(Side::Left).to_dot();
+        assert_eq!(dot.matches(" -> ").count(), 25 * 3);
+        assert!(rees.quotient(&quotient).is_none());
+    }
+}
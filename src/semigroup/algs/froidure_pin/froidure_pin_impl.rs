@@ -1,4 +1,7 @@
-use std::iter::{repeat, repeat_with};
+use std::{
+    iter::successors,
+    time::{Duration, Instant},
+};
 
 use itertools::Itertools;
 
@@ -17,12 +20,27 @@ where
 {
     // Current length of the word we're considering
     current_word_length: usize,
+    // The index in the order of the next element to multiply by the generators, and of the first element of the current length
+    pos: usize,
+    layer_start: usize,
     // Original generators of the semigroup
     generators: Vec<T>,
-    // Elements sorted in military order
+    // If the semigroup supplied an identity, in which case it is stored at position zero.
+    is_monoid: bool,
+    // At index i store the position of generator i in the elements
+    generator_positions: Vec<usize>,
+    // Elements in the order they were first found, which is military order unless generators have been added.
+    // Elements found before generators were added keep their position, and later elements come after them.
     elements: Vec<T>,
     // Map of elements to position in gens
     element_map: DetHashMap<T, usize>,
+    // The positions of the elements in the order they have been found by the current enumeration, which is military order.
+    order: Vec<usize>,
+    // At index i store the index of element i in the order, or None if the current enumeration has not found it yet.
+    order_index: Vec<Option<usize>>,
+    // At index i store how many of the generators element i has been multiplied by on the right.
+    // Those products are read from the right Cayley graph when generators are added, rather than computed again.
+    known_products: Vec<usize>,
     // Rewrite rules that index into the elements
     rewrite_rules: Vec<(Word<usize>, Word<usize>)>,
     // The left and right Cayley graphs, which index into the elements.
@@ -33,15 +51,19 @@ where
     // Various bits of bookkeeping, which can be summarised by
     // elements[i] = prefix[i] * last[i] = first[i] * suffix[i]
     // At index i store the index of what we multiplied on the left by to get the value at index i in the elements
+    // This is None if the prefix is the empty word.
     prefix: Vec<Option<usize>>,
     // At index i store the index of the generator we multiplied on the right by to get the value at index i in the elements
     last: Vec<usize>,
     // At index i store the index of what we multiplied on the right by to get the value at index i in the elements
+    // This is None if the suffix is the empty word.
     suffix: Vec<Option<usize>>,
     // At index i store the index of the generator we multiplied by to get the value at index i in the elements
     first: Vec<usize>,
     // Index i stores the length of the word representing elements[i]
     length: Vec<usize>,
+    // Products for the current layer computed ahead of time, stored at (pos - layer_start) * n_gens + i.
+    precomputed: Vec<Option<T>>,
 }
 
 impl<T> FroidurePin<T>
@@ -52,61 +74,79 @@ where
     where
         U: Semigroup<T>,
     {
+        Self::with_generators(gens.generators(), gens.id())
+    }
+
+    fn with_generators(generators: &[T], identity: Option<T>) -> Self {
         // Filter out duplicate generators and the identity
-        let generators: Vec<T> = gens
-            .generators()
+        let generators: Vec<T> = generators
             .iter()
             .unique()
-            .filter(|s| !s.is_id())
+            .filter(|s| Some(*s) != identity.as_ref())
             .cloned()
             .collect();
-        // Initial elements are just the generators
-        let mut elements = generators.clone();
-        // Insert identity into position zero.
-        elements.insert(0, gens.id().unwrap());
+        let is_monoid = identity.is_some();
+        let mut elements = Vec::with_capacity(generators.len() + 1);
         let mut element_map = DetHashMap::default();
         let rewrite_rules = Vec::new();
         // Vecs for info about each element
+        let mut generator_positions = Vec::with_capacity(generators.len());
         let mut prefix = Vec::new();
         let mut last = Vec::new();
         let mut suffix = Vec::new();
         let mut first = Vec::new();
         let mut length = Vec::new();
-        // Initialise identity
-        prefix.push(None);
-        last.push(0);
-        suffix.push(None);
-        first.push(0);
-        length.push(0);
-        element_map.insert(elements[0].clone(), 0);
-        // Now initialise the above
-        for index in 1..elements.len() {
-            element_map.insert(elements[index].clone(), index);
-            // Prefix of a generator is the empty word
-            prefix.push(Some(0));
+        // Insert identity into position zero if we have one.
+        if let Some(id) = identity {
+            element_map.insert(id.clone(), 0);
+            elements.push(id);
+            // The identity is the empty word, so first and last are never used.
+            prefix.push(None);
+            last.push(0);
+            suffix.push(None);
+            first.push(0);
+            length.push(0);
+        }
+        // Then the generators
+        for (index, generator) in generators.iter().enumerate() {
+            let pos = elements.len();
+            element_map.insert(generator.clone(), pos);
+            elements.push(generator.clone());
+            generator_positions.push(pos);
+            // Prefix and suffix of a generator are the empty word
+            prefix.push(None);
             last.push(index);
-            // Suffix is similarly the empty word
-            suffix.push(Some(0));
+            suffix.push(None);
             first.push(index);
             length.push(1);
         }
         // 2d arrays for the Cayley graphs and if a word is reduced.
-        let mut left_cayley_graph = Vec2::new(elements.len(), elements.len());
-        let mut right_cayley_graph = Vec2::new(elements.len(), elements.len());
+        let mut left_cayley_graph = Vec2::new(elements.len(), generators.len());
+        let mut right_cayley_graph = Vec2::new(elements.len(), generators.len());
         // Initialise identity for the graphs
-        for i in 1..=generators.len() {
-            left_cayley_graph[(i, 0)] = Some(i);
-            left_cayley_graph[(0, i)] = Some(i);
-            right_cayley_graph[(i, 0)] = Some(i);
-            right_cayley_graph[(0, i)] = Some(i);
+        if is_monoid {
+            for (i, &pos) in generator_positions.iter().enumerate() {
+                left_cayley_graph[(0, i)] = Some(pos);
+                right_cayley_graph[(0, i)] = Some(pos);
+            }
         }
-        let reduced = Vec2::new(elements.len(), elements.len());
-        // Other information
+        let reduced = Vec2::new(elements.len(), generators.len());
+        // Every element so far is found in order.
+        let order: Vec<usize> = (0..elements.len()).collect();
+        let order_index = order.iter().copied().map(Some).collect();
+        let known_products = vec![0; elements.len()];
+        // Other information, we start from the first generator
         let current_word_length = 1;
+        let pos = is_monoid as usize;
         Self {
             generators,
+            is_monoid,
+            generator_positions,
             elements,
             element_map,
+            order,
+            order_index,
+            known_products,
             rewrite_rules,
             reduced,
             prefix,
@@ -117,194 +157,453 @@ where
             left_cayley_graph,
             right_cayley_graph,
             current_word_length,
+            pos,
+            layer_start: pos,
+            precomputed: Vec::new(),
         }
     }
 
     /// Given u and x, find v such that ux = v
     fn get_right_cayley_element(&self, element: usize, generator_index: usize) -> Option<usize> {
-        debug_assert!(generator_index != 0 && generator_index <= self.generators.len());
+        debug_assert!(generator_index < self.generators.len());
         self.right_cayley_graph[(element, generator_index)]
     }
 
     /// Given u and x, find v such that xu = v
     fn get_left_cayley_element(&self, element: usize, generator_index: usize) -> Option<usize> {
-        debug_assert!(generator_index != 0 && generator_index <= self.generators.len());
+        debug_assert!(generator_index < self.generators.len());
         self.left_cayley_graph[(element, generator_index)]
     }
 
-    // Convert an index into a word of the generators
+    // Convert an index into a word of the generators, where each letter is an index into the generators.
     fn pos_to_word(&self, pos: usize) -> Word<usize> {
-        let mut cur_pos = pos;
-        // We repeatedly take the last value to create our word
-        repeat_with(move || {
-            let first = self.first[cur_pos];
-            cur_pos = self.suffix[cur_pos].unwrap();
-            first
-        })
-        .take(self.length[pos])
-        .collect()
-    }
-
-    fn run(&mut self) {
-        // First multiply all generators by themselves
-        for i in 1..=self.generators.len() {
-            for j in 1..=self.generators.len() {
-                let product = self.elements[i].multiply(&self.elements[j]);
-                match self.element_map.get(&product) {
-                    Some(&index) => {
-                        // Add rule
+        // We repeatedly take the first value and move onto the suffix to create our word
+        successors(Some(pos), |&cur_pos| self.suffix[cur_pos])
+            .take(self.length[pos])
+            .map(|cur_pos| self.first[cur_pos])
+            .collect()
+    }
+
+    /// Return if every element of the semigroup has been found, and the Cayley graphs are complete.
+    pub fn is_finished(&self) -> bool {
+        self.pos == self.order.len()
+    }
+
+    /// Return the number of elements found so far.
+    pub fn current_size(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Return the number of rewrite rules found so far.
+    pub fn current_number_of_rules(&self) -> usize {
+        self.rewrite_rules.len()
+    }
+
+    /// Return the length of the words currently being multiplied by the generators.
+    /// Every element represented by a word of at most this length has been found.
+    pub fn current_word_length(&self) -> usize {
+        self.current_word_length
+    }
+
+    /// Continue the enumeration until the given condition holds or the enumeration finishes.
+    /// The condition is checked before each element is multiplied by the generators,
+    /// and the return value is if the enumeration has finished.
+    pub fn run_until<F>(&mut self, mut stop: F) -> bool
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        while !self.is_finished() && !stop(self) {
+            self.step();
+        }
+        self.is_finished()
+    }
+
+    /// Continue the enumeration until at least `limit` elements are found, returning if the enumeration has finished.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    /// use yatyat::semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder};
+    /// use yatyat::semigroup::impls::transformation::TransformationSemigroup;
+    ///
+    /// let s = TransformationSemigroup::new(&[
+    ///     Transformation::from_vec(4, vec![1, 0, 2, 3]).unwrap(),
+    ///     Transformation::from_vec(4, vec![1, 2, 3, 0]).unwrap(),
+    ///     Transformation::from_vec(4, vec![0, 0, 2, 3]).unwrap(),
+    /// ])
+    /// .unwrap();
+    /// let mut fp = FroidurePin::new(&s);
+    /// assert!(!fp.enumerate(100));
+    /// assert!(fp.current_size() >= 100);
+    /// // We can then carry on from where we stopped.
+    /// assert!(fp.enumerate(usize::MAX));
+    /// assert_eq!(fp.build().size(), 256);
+    /// ```
+    pub fn enumerate(&mut self, limit: usize) -> bool {
+        self.run_until(|fp| fp.current_size() >= limit)
+    }
+
+    /// Continue the enumeration until every element represented by a word of at most the given length has been found,
+    /// returning if the enumeration has finished.
+    pub fn run_until_word_length(&mut self, length: usize) -> bool {
+        self.run_until(|fp| fp.current_word_length >= length)
+    }
+
+    /// Continue the enumeration for at most the given duration, returning if the enumeration has finished.
+    pub fn run_for(&mut self, duration: Duration) -> bool {
+        let start = Instant::now();
+        self.run_until(|_| start.elapsed() >= duration)
+    }
+
+    /// Return if the element is in the semigroup, enumerating only until it is found or the enumeration finishes.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    /// use yatyat::semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder};
+    /// use yatyat::semigroup::impls::transformation::TransformationSemigroup;
+    ///
+    /// let s = TransformationSemigroup::new(&[
+    ///     Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+    ///     Transformation::from_vec(5, vec![1, 2, 3, 4, 0]).unwrap(),
+    ///     Transformation::from_vec(5, vec![1, 1, 2, 3, 4]).unwrap(),
+    /// ])
+    /// .unwrap();
+    /// let mut fp = FroidurePin::new(&s);
+    /// assert!(fp.contains(&Transformation::from_vec(5, vec![2, 2, 3, 4, 0]).unwrap()));
+    /// assert!(!fp.is_finished());
+    /// ```
+    pub fn contains(&mut self, element: &T) -> bool {
+        self.run_until(|fp| fp.element_map.contains_key(element));
+        self.element_map.contains_key(element)
+    }
+
+    /// Return the position of the element in short-lex order, or None if it is not in the semigroup.
+    /// This enumerates only until the element is found or the enumeration finishes.
+    pub fn position(&mut self, element: &T) -> Option<usize> {
+        let find = |fp: &Self| {
+            fp.element_map
+                .get(element)
+                .and_then(|&pos| fp.order_index[pos])
+        };
+        self.run_until(|fp| find(fp).is_some());
+        find(self)
+    }
+
+    /// Add generators to the semigroup, reusing the products that are already known.
+    ///
+    /// The new generators come after the existing ones, so the result is the same as enumerating with all of the generators
+    /// from the start. New generators can shorten the normal forms of elements already found, so the enumeration
+    /// re-traverses every element from the generators again, and the rewrite rules are found again.
+    /// The elements found so far keep their positions and their rows of the Cayley graphs, which get a new column for
+    /// each new generator, so the products of these elements by the old generators are read from the right Cayley graph
+    /// rather than multiplied. Only the products involving a new generator or a new element are computed.
+    /// The elements are put into short-lex order when the result is built.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    /// use yatyat::semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder};
+    /// use yatyat::semigroup::impls::transformation::TransformationSemigroup;
+    ///
+    /// let f = Transformation::from_vec(4, vec![1, 0, 2, 3]).unwrap();
+    /// let g = Transformation::from_vec(4, vec![1, 2, 3, 0]).unwrap();
+    /// let mut fp = FroidurePin::new(&TransformationSemigroup::new(&[f]).unwrap());
+    /// fp.run();
+    /// assert_eq!(fp.current_size(), 2);
+    /// fp.add_generators(&[g]);
+    /// fp.run();
+    /// assert_eq!(fp.current_size(), 24);
+    /// ```
+    pub fn add_generators(&mut self, generators: &[T]) {
+        let old_n_gens = self.generators.len();
+        let old_size = self.elements.len();
+        // Skip the identity and generators we already have, as when enumerating from the start.
+        for generator in generators {
+            if (self.is_monoid && &self.elements[0] == generator)
+                || self.generators.contains(generator)
+            {
+                continue;
+            }
+            // The generator may be an element we have already found.
+            let pos = match self.element_map.get(generator) {
+                Some(&pos) => pos,
+                None => self.push_element(generator.clone()),
+            };
+            self.generators.push(generator.clone());
+            self.generator_positions.push(pos);
+        }
+        let n_gens = self.generators.len();
+        if n_gens == old_n_gens {
+            return;
+        }
+        let n_new = self.elements.len() - old_size;
+        for graph in [&mut self.right_cayley_graph, &mut self.left_cayley_graph].iter_mut() {
+            graph.add_rows(n_new);
+            graph.add_cols(n_gens - old_n_gens);
+        }
+        // Traverse the elements again from the generators, as their normal forms may have changed.
+        self.order.clear();
+        self.order_index = vec![None; self.elements.len()];
+        if self.is_monoid {
+            self.order.push(0);
+            self.order_index[0] = Some(0);
+            for (i, &pos) in self.generator_positions.iter().enumerate() {
+                self.left_cayley_graph[(0, i)] = Some(pos);
+                self.right_cayley_graph[(0, i)] = Some(pos);
+            }
+        }
+        for (index, &pos) in self.generator_positions.iter().enumerate() {
+            self.order_index[pos] = Some(self.order.len());
+            self.order.push(pos);
+            self.prefix[pos] = None;
+            self.last[pos] = index;
+            self.suffix[pos] = None;
+            self.first[pos] = index;
+            self.length[pos] = 1;
+        }
+        self.reduced = Vec2::new(self.elements.len(), n_gens);
+        self.rewrite_rules.clear();
+        self.precomputed.clear();
+        self.current_word_length = 1;
+        self.pos = self.is_monoid as usize;
+        self.layer_start = self.pos;
+    }
+
+    /// Add the generators that are not already in the semigroup, one at a time.
+    /// This may enumerate the semigroup to decide if a generator is already in it.
+    pub fn closure(&mut self, generators: &[T]) {
+        for generator in generators {
+            if !self.contains(generator) {
+                self.add_generators(std::slice::from_ref(generator));
+            }
+        }
+    }
+
+    /// Run the enumeration to completion.
+    pub fn run(&mut self) {
+        self.run_until(|_| false);
+    }
+
+    /// Multiply the next element by the generators on the right.
+    /// When this is the last element of its length, the left Cayley graph is also filled in for all elements of that length.
+    fn step(&mut self) {
+        let u = self.order[self.pos];
+        debug_assert!(self.length[u] == self.current_word_length);
+        let n_gens = self.generators.len();
+        let first = self.first[u];
+        for i in 0..n_gens {
+            match self.suffix[u] {
+                // If sa_i is not reduced, we can deduce u*a_i from the Cayley graphs
+                Some(suffix) if !self.reduced[(suffix, i)] => {
+                    // We get s*a_i from the right cayley graph.
+                    let suffix_gen = self
+                        .get_right_cayley_element(suffix, i)
+                        .expect("Should be present");
+                    let product = if self.length[suffix_gen] == 0 {
+                        // Identity
+                        self.generator_positions[first]
+                    } else {
+                        let last = self.last[suffix_gen];
+                        // Multiply the prefix on the left by first, which is a generator if the prefix is empty
+                        let first_prefix = match self.prefix[suffix_gen] {
+                            Some(prefix) => self
+                                .get_left_cayley_element(prefix, first)
+                                .expect("Should be present"),
+                            None => self.generator_positions[first],
+                        };
+                        self.get_right_cayley_element(first_prefix, last)
+                            .expect("Should be present")
+                    };
+                    self.right_cayley_graph[(u, i)] = Some(product);
+                }
+                // Otherwise u is a generator or sa_i is reduced, so we need to find the product.
+                _ => match self.find_product(u, i) {
+                    // If the current enumeration has already found this element, add a new rule
+                    Ok(index) if self.order_index[index].is_some() => {
                         let rhs = self.pos_to_word(index);
-                        let lhs = self.pos_to_word(i).append(&j);
+                        let lhs = self.pos_to_word(u).append(&i);
                         self.rewrite_rules.push((lhs, rhs));
-                        // Update cayley graphs
-                        self.right_cayley_graph[(i, j)] = Some(index);
-                        self.left_cayley_graph[(j, i)] = Some(index);
+                        self.right_cayley_graph[(u, i)] = Some(index)
                     }
-
-                    None => {
-                        // Add the new element
-                        let new_pos = self.elements.len();
-                        self.elements.push(product.clone());
-                        self.element_map.insert(product, new_pos);
-                        // Then update first, last, suffix, and prefix
-                        self.first.push(i);
-                        self.last.push(j);
-                        self.prefix.push(Some(i));
-                        self.suffix.push(Some(j));
-                        // Update reduced table
-                        self.reduced.add_row();
-                        self.reduced[(i, j)] = true;
+                    // An element found before generators were added, which is found again here.
+                    Ok(index) => self.found(index, u, i),
+                    // Otherwise we have a new element, so we add to the collection.
+                    Err(product) => {
+                        let new_pos = self.push_element(product);
                         // Update right cayley graph, left cayley graph will be done later
                         self.right_cayley_graph.add_row();
                         self.left_cayley_graph.add_row();
-                        // a_i * a_j = new element
-                        self.right_cayley_graph[(i, j)] = Some(new_pos);
-                        self.left_cayley_graph[(j, i)] = Some(new_pos);
-                        debug_assert!(self.elements.len() == self.element_map.len());
-                        debug_assert!(self.elements.len() == self.first.len());
-                        debug_assert!(self.elements.len() == self.last.len());
-                        debug_assert!(self.elements.len() == self.prefix.len());
-                        debug_assert!(self.elements.len() == self.suffix.len());
-                        // Update length, this is simply one more than u
-                        self.length.push(self.length[i] + 1);
+                        self.reduced.add_row();
+                        self.found(new_pos, u, i);
                     }
-                }
+                },
             }
         }
-        // Then continue unless we found no new elements
-        if self.generators.len() + 1 == self.elements.len() {
-            return;
-        }
-        self.current_word_length = 2;
-        // Take first non generator element
-        let mut u = self.generators.len() + 1;
-        let mut v = u;
-        loop {
-            // Computation of u*a_i
-            while u < self.elements.len() && self.length[u] == self.current_word_length {
-                let first = self.first[u];
-                let suffix = self.suffix[u].expect("Should be larger than 2");
-                // Iterate over the generators to consider products of the form sa_i
-                for i in 1..=self.generators.len() {
-                    // If sa_i is not reduced
-                    if !self.reduced[(suffix, i)] {
-                        // We get s*a_i from the right cayley graph.
-                        let suffix_gen = self
-                            .get_right_cayley_element(suffix, i)
-                            .expect("Should be present");
-                        match suffix_gen {
-                            // Identity
-                            0 => {
-                                debug_assert!(self.right_cayley_graph[(u, i)] == None);
-                                self.right_cayley_graph[(u, i)] = Some(first);
-                            }
-                            // Non identity
-                            _ => {
-                                let last = self.last[suffix_gen];
-                                let prefix = self.prefix[suffix_gen].expect("Should be present");
-                                let first_prefix = self
-                                    .get_left_cayley_element(prefix, first)
-                                    .expect("Should be present");
-                                let first_prefix_last = self
-                                    .get_right_cayley_element(first_prefix, last)
-                                    .expect("Should be present");
-                                debug_assert!(self.right_cayley_graph[(u, i)] == None);
-                                self.right_cayley_graph[(u, i)] = Some(first_prefix_last);
-                            }
-                        }
-                    } else {
-                        let product = self.elements[u].multiply(&self.elements[i]);
-                        match self.element_map.get(&product) {
-                            // If we have already seen this element, add a new rule
-                            Some(&index) => {
-                                // Add rule
-                                let rhs = self.pos_to_word(index);
-                                let lhs = self.pos_to_word(u).append(&i);
-                                self.rewrite_rules.push((lhs, rhs));
-                                // Update right cayley graph
-                                self.right_cayley_graph[(u, i)] = Some(index)
-                            }
-                            // Otherwise we have a new element, so we add to the collection.
-                            None => {
-                                // Add the new element
-                                let new_pos = self.elements.len();
-                                self.elements.push(product.clone());
-                                self.element_map.insert(product, new_pos);
-                                // Then update first, last, suffix, and prefix
-                                self.first.push(first);
-                                self.last.push(i);
-                                self.prefix.push(Some(u));
-                                let suffix = {
-                                    let u_suffix = self.suffix[u].expect("Should be present.");
-                                    self.get_right_cayley_element(u_suffix, i)
-                                        .expect("Should already be present")
-                                };
-                                self.suffix.push(Some(suffix));
-                                // Update reduced table
-                                self.reduced.add_row();
-                                self.reduced[(u, i)] = true;
-                                // Update right cayley graph, left cayley graph will be done later
-                                self.right_cayley_graph.add_row();
-                                self.left_cayley_graph.add_row();
-                                // u * a_i = new element
-                                debug_assert!(self.right_cayley_graph[(u, i)] == None);
-                                self.right_cayley_graph[(u, i)] = Some(new_pos);
-                                // Update length, this is simply one more than u
-                                self.length.push(self.length[u] + 1);
-                            }
-                        }
-                    }
-                }
-                // Go to the next element
-                u += 1;
-            }
-            u = v;
-            // Now compute a_i * u to fill in left cayley graph
-            while u < self.elements.len() && self.length[u] == self.current_word_length {
-                let prefix = self.prefix[u].expect("Should be present.");
+        self.known_products[u] = n_gens;
+        self.pos += 1;
+        // Once every element of the current length has been multiplied on the right,
+        // we can fill in the left Cayley graph for them.
+        if self.pos == self.order.len()
+            || self.length[self.order[self.pos]] != self.current_word_length
+        {
+            for &u in self.order[self.layer_start..self.pos].iter() {
                 let last = self.last[u];
-                for i in 1..=self.generators.len() {
-                    // We work out what a_i * u is from prior information.
-                    let res = {
-                        let ap = self
+                for i in 0..n_gens {
+                    // a_i * u = a_i * prefix * last, where the prefix may be empty
+                    let ap = match self.prefix[u] {
+                        Some(prefix) => self
                             .get_left_cayley_element(prefix, i)
-                            .expect("Should already be computed");
-                        self.get_right_cayley_element(ap, last)
-                            .expect("Should already be computed.")
+                            .expect("Should already be computed"),
+                        None => self.generator_positions[i],
                     };
-                    // a_i * u = res
-                    self.left_cayley_graph[(u, i)] = Some(res);
+                    self.left_cayley_graph[(u, i)] = Some(
+                        self.get_right_cayley_element(ap, last)
+                            .expect("Should already be computed."),
+                    );
                 }
-                // Go onto the next element.
-                u += 1;
             }
-            v = u;
+            self.layer_start = self.pos;
             self.current_word_length += 1;
-            // Break if we've reached the last possible u
-            if u == self.elements.len() {
-                break;
+            self.precomputed.clear();
+        }
+    }
+
+    /// Add a new element, returning its position.
+    /// Its bookkeeping is filled in once the current enumeration finds it, and the rows of the Cayley graphs are not added.
+    fn push_element(&mut self, element: T) -> usize {
+        let pos = self.elements.len();
+        self.elements.push(element.clone());
+        self.element_map.insert(element, pos);
+        self.order_index.push(None);
+        self.known_products.push(0);
+        self.first.push(0);
+        self.last.push(0);
+        self.prefix.push(None);
+        self.suffix.push(None);
+        self.length.push(0);
+        debug_assert!(self.elements.len() == self.element_map.len());
+        debug_assert!(self.elements.len() == self.prefix.len());
+        debug_assert!(self.elements.len() == self.suffix.len());
+        pos
+    }
+
+    /// Record that the current enumeration has found the element at the given position as u * a_i,
+    /// so its normal form is the normal form of u followed by a_i.
+    fn found(&mut self, pos: usize, u: usize, i: usize) {
+        debug_assert!(self.order_index[pos].is_none());
+        self.order_index[pos] = Some(self.order.len());
+        self.order.push(pos);
+        // Then update first, last, suffix, and prefix
+        self.first[pos] = self.first[u];
+        self.last[pos] = i;
+        self.prefix[pos] = Some(u);
+        // The suffix of a generator is the empty word, so the new suffix is a_i
+        let new_suffix = match self.suffix[u] {
+            Some(suffix) => self
+                .get_right_cayley_element(suffix, i)
+                .expect("Should already be present"),
+            None => self.generator_positions[i],
+        };
+        self.suffix[pos] = Some(new_suffix);
+        // Update length, this is simply one more than u
+        self.length[pos] = self.length[u] + 1;
+        self.reduced[(u, i)] = true;
+        self.right_cayley_graph[(u, i)] = Some(pos);
+    }
+
+    /// Compute ahead of time every product u * a_i that will need to be multiplied for the current layer.
+    /// The given function is passed the elements and the pairs of positions to multiply, and returns the products in order.
+    /// This must be called at the start of a layer, and the products are used when the layer is enumerated.
+    pub(super) fn precompute_layer<F>(&mut self, multiply_all: F)
+    where
+        F: FnOnce(&[T], &[(usize, usize)]) -> Vec<T>,
+    {
+        debug_assert!(self.pos == self.layer_start);
+        let n_gens = self.generators.len();
+        let layer_end = (self.pos..self.order.len())
+            .find(|&index| self.length[self.order[index]] != self.current_word_length)
+            .unwrap_or(self.order.len());
+        let mut slots = Vec::new();
+        let mut pairs = Vec::new();
+        for index in self.layer_start..layer_end {
+            let u = self.order[index];
+            for i in 0..n_gens {
+                // These are the same conditions that step and find_product use to decide whether to multiply
+                let multiply = self.suffix[u].map_or(true, |suffix| self.reduced[(suffix, i)]);
+                if multiply && i >= self.known_products[u] {
+                    slots.push((index - self.layer_start) * n_gens + i);
+                    pairs.push((u, self.generator_positions[i]));
+                }
             }
         }
+        let products = multiply_all(&self.elements, &pairs);
+        debug_assert!(products.len() == pairs.len());
+        self.precomputed = std::iter::repeat_with(|| None)
+            .take((layer_end - self.layer_start) * n_gens)
+            .collect();
+        for (slot, product) in slots.into_iter().zip(products) {
+            self.precomputed[slot] = Some(product);
+        }
+    }
+
+    /// Find u * a_i, where u is the element being multiplied, returning its position if it is already an element.
+    /// Otherwise the product is returned.
+    fn find_product(&mut self, u: usize, i: usize) -> Result<usize, T> {
+        // Products by the generators an element was already multiplied by are in the right Cayley graph.
+        if i < self.known_products[u] {
+            return Ok(self.right_cayley_graph[(u, i)].expect("Should already be computed"));
+        }
+        let precomputed = self
+            .precomputed
+            .get_mut((self.pos - self.layer_start) * self.generators.len() + i)
+            .and_then(Option::take);
+        let product = match precomputed {
+            Some(product) => product,
+            None => self.elements[u].multiply(&self.elements[self.generator_positions[i]]),
+        };
+        match self.element_map.get(&product) {
+            Some(&index) => Ok(index),
+            None => Err(product),
+        }
+    }
+
+    /// Move the elements into military order, which they are only out of if generators have been added.
+    /// This must only be called once the enumeration has finished, as then every element has been found.
+    fn sort_elements(&mut self) {
+        if self
+            .order
+            .iter()
+            .enumerate()
+            .all(|(index, &pos)| index == pos)
+        {
+            return;
+        }
+        let position: Vec<usize> = self
+            .order_index
+            .iter()
+            .map(|index| index.expect("Every element is found"))
+            .collect();
+        let mut elements: Vec<Option<T>> = self.elements.drain(..).map(Some).collect();
+        self.elements = self
+            .order
+            .iter()
+            .map(|&pos| elements[pos].take().unwrap())
+            .collect();
+        for pos in self.element_map.values_mut() {
+            *pos = position[*pos];
+        }
+        let n_gens = self.generators.len();
+        for graph in [&mut self.right_cayley_graph, &mut self.left_cayley_graph].iter_mut() {
+            let mut sorted = Vec2::new(self.order.len(), n_gens);
+            for (index, &pos) in self.order.iter().enumerate() {
+                for i in 0..n_gens {
+                    sorted[(index, i)] = graph[(pos, i)].map(|x| position[x]);
+                }
+            }
+            **graph = sorted;
+        }
+        self.first = self.order.iter().map(|&pos| self.first[pos]).collect();
+        self.suffix = self
+            .order
+            .iter()
+            .map(|&pos| self.suffix[pos].map(|x| position[x]))
+            .collect();
+        self.length = self.order.iter().map(|&pos| self.length[pos]).collect();
     }
 }
 
@@ -322,26 +621,34 @@ where
     fn build(mut self) -> FroidurePinResult<T> {
         // Run Froidure-Pin
         self.run();
+        self.sort_elements();
         FroidurePinResult {
             generators: self.generators,
+            is_monoid: self.is_monoid,
             elements: self.elements,
             element_map: self.element_map,
             rewrite_rules: self.rewrite_rules,
             left_cayley_graph: self.left_cayley_graph,
             right_cayley_graph: self.right_cayley_graph,
+            first: self.first,
+            suffix: self.suffix,
+            length: self.length,
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use std::{cell::Cell, time::Duration};
+
     use crate::{
-        element::transformation::Transformation,
+        element::{transformation::Transformation, SemigroupElement},
         semigroup::{
             algs::froidure_pin::{
-                froidure_pin_impl::FroidurePin, simple::FroidurePinSimple, FroidurePinBuilder,
+                cross_check::assert_same_result, froidure_pin_impl::FroidurePin, FroidurePinBuilder,
             },
             impls::transformation::TransformationSemigroup,
+            word::Word,
         },
     };
 
@@ -355,7 +662,144 @@ mod test {
         .unwrap();
         let fp = FroidurePin::new(&s);
         let res = fp.build();
-        dbg!(&res.elements.len());
-        assert!(res.elements.len() == 3125);
+        dbg!(&res.size());
+        assert!(res.size() == 3125);
+    }
+
+    #[test]
+    fn rules_and_left_cayley_graph() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(6, vec![1, 1, 3, 3, 4, 5]).unwrap(),
+            Transformation::from_vec(6, vec![4, 2, 3, 3, 5, 5]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let gens = res.generators();
+        // Evaluate a word using the generators
+        let eval = |w: &Word<usize>| {
+            w.as_vec().iter().fold(Transformation::id(6), |acc, &a| {
+                acc.multiply(&gens[a]).unwrap()
+            })
+        };
+        for (lhs, rhs) in res.rules() {
+            assert!(lhs > rhs);
+            assert_eq!(eval(lhs), eval(rhs));
+        }
+        for (i, x) in res.iter().enumerate() {
+            for (j, gen) in gens.iter().enumerate() {
+                let left = res.left_cayley(i, j).unwrap();
+                assert_eq!(res.element(left).unwrap(), &gen.multiply(x).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn resume_enumeration() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+            Transformation::from_vec(5, vec![1, 2, 3, 4, 0]).unwrap(),
+            Transformation::from_vec(5, vec![1, 1, 2, 3, 4]).unwrap(),
+        ])
+        .unwrap();
+        let mut fp = FroidurePin::new(&s);
+        assert!(!fp.run_for(Duration::ZERO));
+        assert_eq!(fp.current_size(), 4);
+        assert!(!fp.enumerate(1000));
+        assert!(fp.current_size() >= 1000);
+        assert!(!fp.run_until_word_length(8));
+        assert_eq!(fp.current_word_length(), 8);
+        let rules = fp.current_number_of_rules();
+        assert!(!fp.run_until(|fp| fp.current_number_of_rules() > rules));
+        assert!(fp.run_for(Duration::from_secs(3600)));
+        assert!(fp.is_finished());
+        assert!(fp.enumerate(usize::MAX));
+        assert_same_result(&fp.build(), &FroidurePin::new(&s).build());
+    }
+
+    #[test]
+    fn lazy_membership() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+            Transformation::from_vec(5, vec![1, 2, 3, 4, 0]).unwrap(),
+        ])
+        .unwrap();
+        let mut fp = FroidurePin::new(&s);
+        // Generators are known without any enumeration.
+        assert_eq!(fp.position(&Transformation::id(5)), Some(0));
+        assert!(fp.contains(&Transformation::from_vec(5, vec![1, 2, 3, 4, 0]).unwrap()));
+        assert_eq!(fp.current_size(), 3);
+        assert!(fp.contains(&Transformation::from_vec(5, vec![0, 1, 2, 4, 3]).unwrap()));
+        assert!(!fp.is_finished());
+        // An element not in the group forces the whole enumeration.
+        assert!(!fp.contains(&Transformation::from_vec(5, vec![0, 0, 2, 3, 4]).unwrap()));
+        assert!(fp.is_finished());
+        assert_eq!(fp.current_size(), 120);
+    }
+
+    #[test]
+    fn add_generators() {
+        let gens = [
+            Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+            Transformation::from_vec(5, vec![1, 2, 3, 4, 0]).unwrap(),
+            Transformation::from_vec(5, vec![1, 1, 2, 3, 4]).unwrap(),
+        ];
+        let expected = FroidurePin::new(&TransformationSemigroup::new(&gens).unwrap()).build();
+        // Add the generators one at a time, stopping the enumeration at different points.
+        let mut fp = FroidurePin::new(&TransformationSemigroup::new(&gens[..1]).unwrap());
+        fp.run();
+        fp.add_generators(&gens[1..2]);
+        fp.enumerate(50);
+        fp.add_generators(&[Transformation::id(5), gens[2].clone(), gens[0].clone()]);
+        assert_same_result(&fp.build(), &expected);
+        // Closure only adds generators which are not already in the semigroup.
+        let mut fp = FroidurePin::new(&TransformationSemigroup::new(&gens[..2]).unwrap());
+        let cycle = Transformation::from_vec(5, vec![2, 3, 4, 0, 1]).unwrap();
+        fp.closure(&[cycle, gens[2].clone()]);
+        assert_same_result(&fp.build(), &expected);
+    }
+
+    thread_local! {
+        static MULTIPLICATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// Transformation that counts how many times it is multiplied.
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Counted(Transformation);
+
+    impl SemigroupElement for Counted {
+        fn multiply(&self, other: &Self) -> Self {
+            MULTIPLICATIONS.with(|n| n.set(n.get() + 1));
+            Counted(self.0.multiply(&other.0).unwrap())
+        }
+    }
+
+    fn multiplications<F: FnOnce()>(f: F) -> usize {
+        MULTIPLICATIONS.with(|n| n.set(0));
+        f();
+        MULTIPLICATIONS.with(|n| n.get())
+    }
+
+    #[test]
+    fn add_generators_reuses_products() {
+        let gens = [
+            Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+            Transformation::from_vec(5, vec![1, 2, 3, 4, 0]).unwrap(),
+            Transformation::from_vec(5, vec![1, 1, 2, 3, 4]).unwrap(),
+        ];
+        let gens: Vec<_> = gens.iter().cloned().map(Counted).collect();
+        let id = Counted(Transformation::id(5));
+        let mut fp = FroidurePin::with_generators(&gens[..2], Some(id.clone()));
+        let old = multiplications(|| fp.run());
+        assert_eq!(fp.current_size(), 120);
+        let update = multiplications(|| {
+            fp.add_generators(&gens[2..]);
+            fp.run();
+        });
+        let mut scratch = FroidurePin::with_generators(&gens, Some(id));
+        let from_scratch = multiplications(|| scratch.run());
+        // The products found while enumerating the symmetric group are read from the Cayley graph,
+        // so no product is computed twice.
+        assert_eq!(old + update, from_scratch);
+        assert_same_result(&fp.build(), &scratch.build());
     }
 }
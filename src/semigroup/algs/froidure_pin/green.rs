@@ -0,0 +1,288 @@
+use super::{EnumeratedSemigroup, Side};
+use crate::{utils::scc::strongly_connected_components, DetHashMap};
+
+/// A partition of the elements of a semigroup into classes.
+/// Elements are given by their position in the semigroup the classes were computed from,
+/// and classes are numbered in order of their smallest element.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Classes {
+    // At index i store the index of the class containing element i
+    class_index: Vec<usize>,
+    // The elements of each class, in increasing order
+    classes: Vec<Vec<usize>>,
+}
+
+impl Classes {
+    /// Create the classes from the class of each element, which must be numbered in order of their smallest element.
+    pub(super) fn from_class_index(class_index: Vec<usize>) -> Self {
+        let mut classes: Vec<Vec<usize>> = Vec::new();
+        for (element, &class) in class_index.iter().enumerate() {
+            if class == classes.len() {
+                classes.push(Vec::new());
+            }
+            classes[class].push(element);
+        }
+        Self {
+            class_index,
+            classes,
+        }
+    }
+
+    /// Return the number of classes.
+    pub fn number_of_classes(&self) -> usize {
+        self.classes.len()
+    }
+
+    /// Return the number of elements that are partitioned into classes.
+    pub fn number_of_elements(&self) -> usize {
+        self.class_index.len()
+    }
+
+    /// Return all of the classes.
+    pub fn classes(&self) -> &[Vec<usize>] {
+        &self.classes[..]
+    }
+
+    /// Return the class with the given index, or None if the index is out of range.
+    pub fn class(&self, index: usize) -> Option<&[usize]> {
+        self.classes.get(index).map(|class| &class[..])
+    }
+
+    /// Return the index of the class containing the element at the given position, or None if the position is out of range.
+    pub fn class_index(&self, element: usize) -> Option<usize> {
+        self.class_index.get(element).copied()
+    }
+
+    /// Return the class containing the element at the given position, or None if the position is out of range.
+    pub fn class_of(&self, element: usize) -> Option<&[usize]> {
+        self.class(self.class_index(element)?)
+    }
+
+    /// Return a representative of each class, which is its smallest element.
+    pub fn representatives(&self) -> Vec<usize> {
+        self.classes.iter().map(|class| class[0]).collect()
+    }
+
+    /// Return the size of each class.
+    pub fn sizes(&self) -> Vec<usize> {
+        self.classes.iter().map(|class| class.len()).collect()
+    }
+}
+
+/// Green's relations of a finite semigroup, computed from the Cayley graphs of an enumerated semigroup.
+///
+/// The R-classes and L-classes are the strongly connected components of the right and left Cayley graphs,
+/// the H-classes are their intersections, and the D-classes are the strongly connected components of both graphs together.
+/// As the semigroup is finite, the D-classes are also the J-classes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GreensRelations {
+    r_classes: Classes,
+    l_classes: Classes,
+    h_classes: Classes,
+    d_classes: Classes,
+    // At index i store the other D-classes reached from D-class i by multiplying by a generator on either side,
+    // in increasing order. The J-order is the reflexive transitive closure of these edges.
+    edges: Vec<Vec<usize>>,
+}
+
+impl GreensRelations {
+    /// Compute Green's relations from the Cayley graphs of the semigroup.
+    pub fn new<S>(result: &S) -> Self
+    where
+        S: EnumeratedSemigroup,
+    {
+        let (size, n_gens) = (result.size(), result.number_of_generators());
+        let components = |sides: &[Side]| {
+            // Edges are numbered by side and then generator.
+            Classes::from_class_index(strongly_connected_components(
+                size,
+                sides.len() * n_gens,
+                |x, e| result.cayley(sides[e / n_gens], x, e % n_gens),
+            ))
+        };
+        let r_classes = components(&[Side::Right]);
+        let l_classes = components(&[Side::Left]);
+        let d_classes = components(&[Side::Right, Side::Left]);
+        // An H-class is the intersection of an R-class and an L-class.
+        let mut h_map = DetHashMap::default();
+        let h_index = r_classes
+            .class_index
+            .iter()
+            .zip(l_classes.class_index.iter())
+            .map(|pair| {
+                let next = h_map.len();
+                *h_map.entry(pair).or_insert(next)
+            })
+            .collect();
+        let h_classes = Classes::from_class_index(h_index);
+        // Collapse the Cayley graphs onto the D-classes.
+        let mut edges = vec![Vec::new(); d_classes.number_of_classes()];
+        for (element, &class) in d_classes.class_index.iter().enumerate() {
+            for side in [Side::Right, Side::Left] {
+                for a in 0..n_gens {
+                    if let Some(target) = result.cayley(side, element, a) {
+                        let target = d_classes.class_index[target];
+                        if target != class {
+                            edges[class].push(target);
+                        }
+                    }
+                }
+            }
+        }
+        for targets in edges.iter_mut() {
+            targets.sort_unstable();
+            targets.dedup();
+        }
+        Self {
+            r_classes,
+            l_classes,
+            h_classes,
+            d_classes,
+            edges,
+        }
+    }
+
+    // Mark the D-classes reachable from the given D-class, which must be in range.
+    fn reachable(&self, class: usize) -> Vec<bool> {
+        let mut seen = vec![false; self.edges.len()];
+        seen[class] = true;
+        let mut stack = vec![class];
+        while let Some(c) = stack.pop() {
+            for &next in self.edges[c].iter() {
+                if !seen[next] {
+                    seen[next] = true;
+                    stack.push(next);
+                }
+            }
+        }
+        seen
+    }
+
+    /// Return the R-classes, where x R y if xS^1 = yS^1.
+    pub fn r_classes(&self) -> &Classes {
+        &self.r_classes
+    }
+
+    /// Return the L-classes, where x L y if S^1x = S^1y.
+    pub fn l_classes(&self) -> &Classes {
+        &self.l_classes
+    }
+
+    /// Return the H-classes, which are the intersections of the R-classes and L-classes.
+    pub fn h_classes(&self) -> &Classes {
+        &self.h_classes
+    }
+
+    /// Return the D-classes, which are the equivalence classes of the join of R and L.
+    pub fn d_classes(&self) -> &Classes {
+        &self.d_classes
+    }
+
+    /// Return the J-classes, where x J y if S^1xS^1 = S^1yS^1.
+    /// These are the same as the D-classes, as the semigroup is finite.
+    pub fn j_classes(&self) -> &Classes {
+        &self.d_classes
+    }
+
+    /// Return if J-class a is at most J-class b, i.e if the ideal generated by a is contained in the ideal generated by b.
+    /// False is returned if either index is out of range.
+    pub fn j_leq(&self, a: usize, b: usize) -> bool {
+        a < self.edges.len() && b < self.edges.len() && self.reachable(b)[a]
+    }
+
+    /// Return the J-classes at most the given J-class, including itself, in increasing order,
+    /// or None if the index is out of range.
+    pub fn j_classes_below(&self, class: usize) -> Option<Vec<usize>> {
+        if class >= self.edges.len() {
+            return None;
+        }
+        let seen = self.reachable(class);
+        Some((0..seen.len()).filter(|&c| seen[c]).collect())
+    }
+
+    /// Return if the given J-class is minimal in the J-order, i.e. no other J-class is below it.
+    /// False is returned if the index is out of range.
+    pub fn is_j_minimal(&self, class: usize) -> bool {
+        self.edges
+            .get(class)
+            .is_some_and(|targets| targets.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GreensRelations;
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{
+                froidure_pin_impl::FroidurePin, EnumeratedSemigroup, FroidurePinBuilder,
+            },
+            impls::transformation::TransformationSemigroup,
+        },
+    };
+
+    #[test]
+    fn full_transformation_monoid_3() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        assert_eq!(res.size(), 27);
+        let green = res.greens_relations();
+        // The D-classes are the transformations of each rank, starting with the identity.
+        assert_eq!(green.d_classes().sizes(), vec![6, 18, 3]);
+        assert_eq!(green.j_classes(), green.d_classes());
+        // R-classes are given by kernels, L-classes by images.
+        assert_eq!(green.r_classes().number_of_classes(), 1 + 3 + 1);
+        assert_eq!(green.l_classes().number_of_classes(), 1 + 3 + 3);
+        assert_eq!(green.h_classes().number_of_classes(), 1 + 9 + 3);
+        assert_eq!(green.h_classes().class_of(0).unwrap().len(), 6);
+        for (class, rep) in green.d_classes().representatives().into_iter().enumerate() {
+            assert_eq!(green.d_classes().class_index(rep), Some(class));
+            let rank = res
+                .element(rep)
+                .unwrap()
+                .as_vec()
+                .iter()
+                .collect::<std::collections::HashSet<_>>()
+                .len();
+            assert_eq!(rank, 3 - class);
+        }
+        // The J-order is a chain by rank.
+        assert!(green.j_leq(2, 1) && green.j_leq(1, 0) && green.j_leq(2, 0));
+        assert!(!green.j_leq(0, 1));
+        assert_eq!(green.j_classes_below(1), Some(vec![1, 2]));
+        assert!(green.is_j_minimal(2) && !green.is_j_minimal(1) && !green.is_j_minimal(3));
+        assert_eq!(green.j_classes_below(3), None);
+    }
+
+    #[test]
+    fn agrees_with_definition() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(4, vec![1, 0, 3, 3]).unwrap(),
+            Transformation::from_vec(4, vec![2, 2, 0, 1]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let green = GreensRelations::new(&res);
+        // x R y iff each is a right multiple of the other in S^1.
+        let right_ideal = |x: usize| {
+            let x = res.element(x).unwrap();
+            let mut ideal: Vec<_> = res.iter().map(|s| x.multiply(s).unwrap()).collect();
+            ideal.push(x.clone());
+            ideal.sort_by_key(|y| res.position(y));
+            ideal.dedup();
+            ideal
+        };
+        for x in 0..res.size() {
+            for y in 0..res.size() {
+                let same = green.r_classes().class_index(x) == green.r_classes().class_index(y);
+                assert_eq!(same, right_ideal(x) == right_ideal(y));
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use super::{
+    congruence::{Congruence, CongruenceKind, Quotient},
+    green::GreensRelations,
+    EnumeratedSemigroup, Side,
+};
+
+/// Which sides an ideal is closed under multiplication on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdealKind {
+    /// Closed under multiplication on the right, so xS is contained in the ideal for each x in it.
+    Right,
+    /// Closed under multiplication on the left, so Sx is contained in the ideal for each x in it.
+    Left,
+    /// Closed under multiplication on both sides.
+    TwoSided,
+}
+
+/// A right, left or two-sided ideal of a semigroup.
+/// Elements are given by their position in the semigroup the ideal was computed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ideal {
+    kind: IdealKind,
+    // The elements in increasing order
+    elements: Vec<usize>,
+    // At index i store if element i is in the ideal
+    contains: Vec<bool>,
+}
+
+impl Ideal {
+    /// Compute the least ideal of the given kind containing the elements at the given positions,
+    /// or return None if any of the positions are out of range.
+    ///
+    /// This is the closure of the elements under the Cayley graphs on the sides given by the kind of ideal.
+    pub fn new<S>(result: &S, kind: IdealKind, generators: &[usize]) -> Option<Self>
+    where
+        S: EnumeratedSemigroup,
+    {
+        if generators.iter().any(|&x| x >= result.size()) {
+            return None;
+        }
+        let sides: &[Side] = match kind {
+            IdealKind::Right => &[Side::Right],
+            IdealKind::Left => &[Side::Left],
+            IdealKind::TwoSided => &[Side::Right, Side::Left],
+        };
+        let mut contains = vec![false; result.size()];
+        let mut stack = Vec::new();
+        for &x in generators.iter() {
+            if !contains[x] {
+                contains[x] = true;
+                stack.push(x);
+            }
+        }
+        while let Some(x) = stack.pop() {
+            for &side in sides.iter() {
+                for a in 0..result.number_of_generators() {
+                    if let Some(y) = result.cayley(side, x, a) {
+                        if !contains[y] {
+                            contains[y] = true;
+                            stack.push(y);
+                        }
+                    }
+                }
+            }
+        }
+        Some(Self::from_contains(kind, contains))
+    }
+
+    fn from_contains(kind: IdealKind, contains: Vec<bool>) -> Self {
+        let elements = (0..contains.len()).filter(|&x| contains[x]).collect();
+        Self {
+            kind,
+            elements,
+            contains,
+        }
+    }
+
+    /// Compute the minimal ideal, which is the least two-sided ideal.
+    /// This is the unique J-class that is below every other J-class.
+    /// None is returned if the semigroup has no elements, as then there are no J-classes.
+    pub fn minimal<S>(result: &S) -> Option<Self>
+    where
+        S: EnumeratedSemigroup,
+    {
+        let green = GreensRelations::new(result);
+        let minimal =
+            (0..green.j_classes().number_of_classes()).find(|&class| green.is_j_minimal(class))?;
+        let mut contains = vec![false; result.size()];
+        for &x in green.j_classes().class(minimal).unwrap() {
+            contains[x] = true;
+        }
+        Some(Self::from_contains(IdealKind::TwoSided, contains))
+    }
+
+    /// Return the kind of ideal.
+    pub fn kind(&self) -> IdealKind {
+        self.kind
+    }
+
+    /// Return the positions of the elements of the ideal, in increasing order.
+    pub fn elements(&self) -> &[usize] {
+        &self.elements[..]
+    }
+
+    /// Return the number of elements in the ideal.
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Return if the ideal has no elements, which is only the case if it was generated by no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Return if the element at the given position is in the ideal.
+    /// False is returned if the position is out of range.
+    pub fn contains(&self, element: usize) -> bool {
+        self.contains.get(element).copied().unwrap_or(false)
+    }
+
+    /// Return the Rees quotient S/I, where the elements of the ideal are identified to a zero and every other element is kept.
+    /// The quotient is only defined for non-empty two-sided ideals, so None is returned otherwise.
+    pub fn rees_quotient<S>(&self, result: &S) -> Option<Quotient>
+    where
+        S: EnumeratedSemigroup,
+    {
+        if self.kind != IdealKind::TwoSided || self.contains.len() != result.size() {
+            return None;
+        }
+        let (&first, rest) = self.elements.split_first()?;
+        let pairs: Vec<_> = rest.iter().map(|&x| (first, x)).collect();
+        Congruence::new(result, CongruenceKind::TwoSided, &pairs)?.quotient(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ideal, IdealKind};
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{
+                froidure_pin_impl::FroidurePin, EnumeratedSemigroup, FroidurePinBuilder,
+            },
+            algs::todd_coxeter::ToddCoxeter,
+            impls::transformation::TransformationSemigroup,
+            presentation::Presentation,
+        },
+    };
+
+    fn rank(f: &Transformation) -> usize {
+        f.as_vec()
+            .iter()
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    }
+
+    #[test]
+    fn ideals() {
+        // The full transformation monoid of degree 3.
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let f = Transformation::from_vec(3, vec![0, 0, 2]).unwrap();
+        // The two-sided ideal of a rank 2 transformation is everything of rank at most 2.
+        let ideal = res
+            .ideal(IdealKind::TwoSided, std::slice::from_ref(&f))
+            .unwrap();
+        assert_eq!(ideal.kind(), IdealKind::TwoSided);
+        assert_eq!(ideal.size(), 21);
+        for (x, g) in res.iter().enumerate() {
+            assert_eq!(ideal.contains(x), rank(g) <= 2);
+        }
+        // fS^1 has the transformations with kernel coarser than f, and S^1f those with image inside {0, 2}.
+        assert_eq!(
+            res.ideal(IdealKind::Right, std::slice::from_ref(&f))
+                .unwrap()
+                .size(),
+            6 + 3
+        );
+        assert_eq!(res.ideal(IdealKind::Left, &[f]).unwrap().size(), 8);
+        assert!(res
+            .ideal(IdealKind::Left, &[Transformation::id(4)])
+            .is_none());
+        let empty = Ideal::new(&res, IdealKind::TwoSided, &[]).unwrap();
+        assert!(empty.is_empty() && !empty.contains(0));
+        assert!(empty.rees_quotient(&res).is_none());
+        // The minimal ideal is the constant transformations.
+        let minimal = res.minimal_ideal().unwrap();
+        assert_eq!(minimal.size(), 3);
+        assert!(minimal
+            .elements()
+            .iter()
+            .all(|&x| rank(res.element(x).unwrap()) == 1));
+    }
+
+    #[test]
+    fn rees_quotient() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let ideal = Ideal::new(&res, IdealKind::TwoSided, &[res.size() - 1]).unwrap();
+        let quotient = ideal.rees_quotient(&res).unwrap();
+        assert_eq!(quotient.size(), res.size() - ideal.size() + 1);
+        assert!(quotient.is_monoid());
+        let zero = quotient.zero().unwrap();
+        for a in 0..quotient.number_of_generators() {
+            assert_eq!(quotient.right_cayley(zero, a), Some(zero));
+            assert_eq!(quotient.left_cayley(zero, a), Some(zero));
+        }
+        // Only the elements outside the ideal are kept.
+        assert_eq!(quotient.generator_position(2), Some(3));
+        assert!(Ideal::new(&res, IdealKind::Right, &[0])
+            .unwrap()
+            .rees_quotient(&res)
+            .is_none());
+        // The Rees quotient by the whole semigroup is trivial.
+        let whole = Ideal::new(&res, IdealKind::TwoSided, &[0]).unwrap();
+        assert_eq!(whole.size(), res.size());
+        assert_eq!(whole.rees_quotient(&res).unwrap().size(), 1);
+    }
+
+    #[test]
+    fn empty_semigroup() {
+        // The semigroup presented with no generators has no elements, so it has no minimal ideal.
+        let p = Presentation::<usize>::new(vec![], vec![], false).unwrap();
+        let res = ToddCoxeter::new(&p).build().unwrap();
+        assert_eq!(res.size(), 0);
+        assert_eq!(res.minimal_ideal(), None);
+        assert_eq!(Ideal::minimal(&res), None);
+    }
+}
@@ -0,0 +1,245 @@
+use std::fmt::Write;
+
+use super::{
+    congruence::{Congruence, CongruenceKind},
+    EnumeratedSemigroup,
+};
+use crate::DetHashMap;
+
+/// The lattice of all right, left or two-sided congruences of a finite semigroup.
+///
+/// Every congruence is a join of principal congruences, which are generated by a single pair,
+/// so the congruences are found by joining the principal congruences until nothing new is found.
+/// Congruences are ordered by decreasing number of classes, so the trivial congruence is first and the universal
+/// congruence is last.
+#[derive(Debug, Clone)]
+pub struct CongruenceLattice {
+    kind: CongruenceKind,
+    congruences: Vec<Congruence>,
+    // Map from each congruence to its position
+    index: DetHashMap<Congruence, usize>,
+    // The positions of the principal congruences, in increasing order
+    principal: Vec<usize>,
+}
+
+impl CongruenceLattice {
+    /// Compute every congruence of the given kind.
+    pub fn new<S>(result: &S, kind: CongruenceKind) -> Self
+    where
+        S: EnumeratedSemigroup,
+    {
+        let trivial = Congruence::new(result, kind, &[]).unwrap();
+        let mut congruences = vec![trivial.clone()];
+        let mut index = DetHashMap::default();
+        index.insert(trivial, 0);
+        // Add each congruence the first time it is found, and return its position.
+        let mut add = |congruence: Congruence, congruences: &mut Vec<Congruence>| {
+            let next = congruences.len();
+            *index.entry(congruence).or_insert_with_key(|congruence| {
+                congruences.push(congruence.clone());
+                next
+            })
+        };
+        let mut principal = Vec::new();
+        for x in 0..result.size() {
+            for y in x + 1..result.size() {
+                let congruence = Congruence::new(result, kind, &[(x, y)]).unwrap();
+                principal.push(add(congruence, &mut congruences));
+            }
+        }
+        principal.sort_unstable();
+        principal.dedup();
+        let mut next = 0;
+        while next < congruences.len() {
+            for &p in principal.iter() {
+                // Joining with a congruence that is already contained gives nothing new.
+                if !congruences[p].is_contained_in(&congruences[next]) {
+                    let join = congruences[next].join(&congruences[p]).unwrap();
+                    add(join, &mut congruences);
+                }
+            }
+            next += 1;
+        }
+        // Reorder by decreasing number of classes, keeping the positions in the index and principal congruences.
+        let mut numbered: Vec<_> = congruences.into_iter().enumerate().collect();
+        numbered.sort_by_key(|(_, c)| std::cmp::Reverse(c.number_of_classes()));
+        let mut position = vec![0; numbered.len()];
+        for (new, &(old, _)) in numbered.iter().enumerate() {
+            position[old] = new;
+        }
+        let congruences: Vec<Congruence> = numbered.into_iter().map(|(_, c)| c).collect();
+        for i in index.values_mut() {
+            *i = position[*i];
+        }
+        let mut principal: Vec<usize> = principal.iter().map(|&p| position[p]).collect();
+        principal.sort_unstable();
+        Self {
+            kind,
+            congruences,
+            index,
+            principal,
+        }
+    }
+
+    /// Return the kind of congruences in the lattice.
+    pub fn kind(&self) -> CongruenceKind {
+        self.kind
+    }
+
+    /// Return all of the congruences.
+    pub fn congruences(&self) -> &[Congruence] {
+        &self.congruences[..]
+    }
+
+    /// Return the number of congruences.
+    pub fn number_of_congruences(&self) -> usize {
+        self.congruences.len()
+    }
+
+    /// Return the position of the congruence in the lattice, or None if it is not a congruence of the semigroup and kind.
+    pub fn position(&self, congruence: &Congruence) -> Option<usize> {
+        self.index.get(congruence).copied()
+    }
+
+    /// Return the positions of the principal congruences, which are generated by a single pair of distinct elements.
+    pub fn principal_congruences(&self) -> &[usize] {
+        &self.principal[..]
+    }
+
+    /// Return if the congruence at position a is contained in the congruence at position b.
+    /// False is returned if either position is out of range.
+    pub fn leq(&self, a: usize, b: usize) -> bool {
+        match (self.congruences.get(a), self.congruences.get(b)) {
+            (Some(x), Some(y)) => x.is_contained_in(y),
+            _ => false,
+        }
+    }
+
+    /// Return the position of the join of the congruences at the given positions, or None if either is out of range.
+    pub fn join(&self, a: usize, b: usize) -> Option<usize> {
+        let join = self.congruences.get(a)?.join(self.congruences.get(b)?)?;
+        self.position(&join)
+    }
+
+    /// Return the position of the meet of the congruences at the given positions, or None if either is out of range.
+    pub fn meet(&self, a: usize, b: usize) -> Option<usize> {
+        let meet = self.congruences.get(a)?.meet(self.congruences.get(b)?)?;
+        self.position(&meet)
+    }
+
+    /// Return the edges (a, b) of the Hasse diagram, where the congruence at position b covers the one at position a.
+    /// The edges are ordered by a and then b.
+    pub fn hasse_diagram(&self) -> Vec<(usize, usize)> {
+        let n = self.congruences.len();
+        let above: Vec<Vec<usize>> = (0..n)
+            .map(|a| (a + 1..n).filter(|&b| self.leq(a, b)).collect())
+            .collect();
+        // b covers a if nothing is strictly between them. Anything between them has fewer classes than a and more
+        // than b, so it comes between them in the order.
+        (0..n)
+            .flat_map(|a| {
+                let above = &above;
+                above[a].iter().filter_map(move |&b| {
+                    let between = above[a].iter().any(|&c| c < b && above[c].contains(&b));
+                    if between {
+                        None
+                    } else {
+                        Some((a, b))
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Write the Hasse diagram in Graphviz DOT format, with each congruence labelled by its number of classes.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n  rankdir=BT;\n");
+        for (i, congruence) in self.congruences.iter().enumerate() {
+            writeln!(
+                out,
+                "  {} [label=\"{}\"];",
+                i,
+                congruence.number_of_classes()
+            )
+            .unwrap();
+        }
+        for (a, b) in self.hasse_diagram() {
+            writeln!(out, "  {} -> {} [arrowhead=none];", a, b).unwrap();
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CongruenceLattice;
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{
+                congruence::CongruenceKind, froidure_pin_impl::FroidurePin, EnumeratedSemigroup,
+                FroidurePinBuilder,
+            },
+            impls::transformation::TransformationSemigroup,
+        },
+    };
+
+    #[test]
+    fn symmetric_group() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        // Two-sided congruences of a group correspond to normal subgroups.
+        let lattice = res.congruence_lattice(CongruenceKind::TwoSided);
+        assert_eq!(lattice.kind(), CongruenceKind::TwoSided);
+        let sizes: Vec<_> = lattice
+            .congruences()
+            .iter()
+            .map(|c| c.number_of_classes())
+            .collect();
+        assert_eq!(sizes, vec![6, 2, 1]);
+        assert_eq!(lattice.hasse_diagram(), vec![(0, 1), (1, 2)]);
+        assert_eq!(lattice.principal_congruences(), &[1, 2]);
+        // Right congruences correspond to subgroups, whose lattice has the trivial group,
+        // three subgroups of order 2 and one of order 3 as atoms, and the whole group.
+        let lattice = res.congruence_lattice(CongruenceKind::Right);
+        assert_eq!(lattice.number_of_congruences(), 6);
+        let hasse = lattice.hasse_diagram();
+        assert_eq!(hasse.len(), 8);
+        assert_eq!(lattice.join(1, 2), Some(5));
+        assert_eq!(lattice.meet(1, 2), Some(0));
+        assert!(lattice.leq(0, 3) && !lattice.leq(3, 0));
+        assert_eq!(lattice.join(0, 6), None);
+        let dot = lattice.to_dot();
+        assert!(dot.starts_with("digraph {\n  rankdir=BT;\n  0 [label=\"6\"];\n"));
+        assert_eq!(dot.matches("arrowhead=none").count(), 8);
+    }
+
+    #[test]
+    fn lattice_operations() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let lattice = CongruenceLattice::new(&res, CongruenceKind::TwoSided);
+        // The congruences of the full transformation monoid of degree 3, found by Malcev.
+        assert_eq!(lattice.number_of_congruences(), 7);
+        let n = lattice.number_of_congruences();
+        for a in 0..n {
+            for b in 0..n {
+                let join = lattice.join(a, b).unwrap();
+                let meet = lattice.meet(a, b).unwrap();
+                assert!(lattice.leq(a, join) && lattice.leq(b, join));
+                assert!(lattice.leq(meet, a) && lattice.leq(meet, b));
+                assert_eq!(lattice.leq(a, b), join == b);
+            }
+        }
+    }
+}
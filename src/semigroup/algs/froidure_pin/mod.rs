@@ -1,30 +1,607 @@
+use std::{hash::Hash, iter::successors};
+
 use crate::{
     element::SemigroupElement,
-    semigroup::{word::Word, Semigroup},
+    semigroup::{presentation::Presentation, word::Word, Semigroup},
     utils::vec2::Vec2,
     DetHashMap,
 };
 
+pub mod cayley;
+pub mod congruence;
 pub mod froidure_pin_impl;
+pub mod green;
+pub mod ideal;
+pub mod lattice;
+pub mod parallel;
 pub mod simple;
 
+use cayley::CayleyGraph;
+use congruence::{Congruence, CongruenceKind};
+use green::GreensRelations;
+use ideal::{Ideal, IdealKind};
+use lattice::CongruenceLattice;
+
 type CayleyGraphType = Vec2<Option<usize>>;
 
+/// Which side generators multiply elements on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// A finite semigroup whose elements are given by their positions, such as the result of an enumeration.
+///
+/// Elements are numbered in short-lex order of their normal forms, so the identity of a monoid is at position zero,
+/// and products with the generators are given by the left and right Cayley graphs.
+/// Green's relations, congruences, ideals and exports of the Cayley graphs only need these,
+/// so they can be computed for anything that implements this trait.
+pub trait EnumeratedSemigroup {
+    /// Return the number of elements.
+    fn size(&self) -> usize;
+
+    /// Return if the semigroup is a monoid, in which case the identity is at position zero and is represented by the empty word.
+    fn is_monoid(&self) -> bool;
+
+    /// Return the number of generators.
+    fn number_of_generators(&self) -> usize;
+
+    /// Return the position of the generator, or None if the index is out of range.
+    fn generator_position(&self, generator: usize) -> Option<usize>;
+
+    /// Return the position of `element * generator`, where `generator` is an index into the generators.
+    /// None is returned if either index is out of range.
+    fn right_cayley(&self, element: usize, generator: usize) -> Option<usize>;
+
+    /// Return the position of `generator * element`, where `generator` is an index into the generators.
+    /// None is returned if either index is out of range.
+    fn left_cayley(&self, element: usize, generator: usize) -> Option<usize>;
+
+    /// Return the short-lex least word over the generators that represents the element at the given position.
+    /// The identity of a monoid is represented by the empty word. None is returned if the position is out of range.
+    fn normal_form(&self, index: usize) -> Option<Word<usize>>;
+
+    /// Return the position of the element multiplied by the generator on the given side.
+    /// None is returned if either index is out of range.
+    fn cayley(&self, side: Side, element: usize, generator: usize) -> Option<usize> {
+        match side {
+            Side::Left => self.left_cayley(element, generator),
+            Side::Right => self.right_cayley(element, generator),
+        }
+    }
+
+    /// Return the short-lex normal forms of the elements, in order.
+    fn normal_forms(&self) -> Vec<Word<usize>> {
+        (0..self.size())
+            .filter_map(|index| self.normal_form(index))
+            .collect()
+    }
+
+    /// Return the position of the element that a word over the generators evaluates to, using the right Cayley graph.
+    /// None is returned if a letter is not the index of a generator, or the word is empty and the semigroup is not a monoid.
+    fn word_to_position(&self, word: &Word<usize>) -> Option<usize> {
+        let (&first, rest) = match word.as_vec().split_first() {
+            Some(split) => split,
+            None => return if self.is_monoid() { Some(0) } else { None },
+        };
+        rest.iter()
+            .try_fold(self.generator_position(first)?, |pos, &letter| {
+                self.right_cayley(pos, letter)
+            })
+    }
+
+    /// Return the position of the zero, or None if there is no zero.
+    /// This is the element that every generator fixes by multiplying on either side.
+    fn zero(&self) -> Option<usize> {
+        (0..self.size()).find(|&x| {
+            (0..self.number_of_generators())
+                .all(|a| self.right_cayley(x, a) == Some(x) && self.left_cayley(x, a) == Some(x))
+        })
+    }
+
+    /// Compute Green's relations of the semigroup from the Cayley graphs.
+    fn greens_relations(&self) -> GreensRelations
+    where
+        Self: Sized,
+    {
+        GreensRelations::new(self)
+    }
+
+    /// Compute every congruence of the given kind, along with the lattice they form.
+    fn congruence_lattice(&self, kind: CongruenceKind) -> CongruenceLattice
+    where
+        Self: Sized,
+    {
+        CongruenceLattice::new(self, kind)
+    }
+
+    /// Compute the minimal ideal of the semigroup, which is contained in every two-sided ideal,
+    /// or return None if the semigroup has no elements.
+    fn minimal_ideal(&self) -> Option<Ideal>
+    where
+        Self: Sized,
+    {
+        Ideal::minimal(self)
+    }
+
+    /// Return the left or right Cayley graph, which can be exported as DOT, an adjacency list or CSV.
+    fn cayley_graph(&self, side: Side) -> CayleyGraph<'_, Self>
+    where
+        Self: Sized,
+    {
+        CayleyGraph::new(self, side)
+    }
+}
+
+/// Return the normal form of the element at the given position, where the normal form of each element is
+/// the normal form of the element at the first position of its prefix followed by the generator,
+/// and None is the empty word. None is returned if the position is out of range.
+pub(crate) fn prefix_normal_form(
+    prefix: &[Option<(Option<usize>, usize)>],
+    index: usize,
+) -> Option<Word<usize>> {
+    let mut word: Vec<usize> = successors(prefix.get(index).copied()?, |&(previous, _)| {
+        previous.and_then(|y| prefix[y])
+    })
+    .map(|(_, a)| a)
+    .collect();
+    word.reverse();
+    Some(word.into_iter().collect())
+}
+
+/// Return the entry of a Cayley graph, or None if either index is out of range.
+pub(crate) fn cayley_lookup(
+    graph: &CayleyGraphType,
+    element: usize,
+    generator: usize,
+) -> Option<usize> {
+    if element < graph.n_rows() && generator < graph.n_cols() {
+        graph[(element, generator)]
+    } else {
+        None
+    }
+}
+
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(
+        try_from = "serde_impl::ResultData<U>",
+        bound(deserialize = "U: SemigroupElement + Hash + serde::Deserialize<'de>")
+    )
+)]
 pub struct FroidurePinResult<U>
 where
     U: SemigroupElement,
 {
     generators: Vec<U>,
+    // If the identity was supplied by the semigroup, in which case it is at position zero.
+    is_monoid: bool,
     // Elements sorted in military order
     elements: Vec<U>,
-    // Map of elements to position in gens
+    // Map of elements to position in gens, which is rebuilt from the elements when deserializing
+    #[cfg_attr(feature = "serde", serde(skip_serializing))]
     element_map: DetHashMap<U, usize>,
     // Rewrite rules that index into the elements
     rewrite_rules: Vec<(Word<usize>, Word<usize>)>,
     // The left and right Cayley graphs, which index into the elements.
     left_cayley_graph: CayleyGraphType,
     right_cayley_graph: CayleyGraphType,
+    // Bookkeeping to recover the normal form of each element, where elements[i] = first[i] * suffix[i]
+    first: Vec<usize>,
+    suffix: Vec<Option<usize>>,
+    length: Vec<usize>,
+}
+
+impl<U> FroidurePinResult<U>
+where
+    U: SemigroupElement + Hash,
+{
+    /// Return the number of elements in the semigroup.
+    pub fn size(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Return the element at the given position in short-lex order, or None if the position is out of range.
+    pub fn element(&self, index: usize) -> Option<&U> {
+        self.elements.get(index)
+    }
+
+    /// Return the position of an element in short-lex order, or None if it is not in the semigroup.
+    pub fn position(&self, element: &U) -> Option<usize> {
+        self.element_map.get(element).copied()
+    }
+
+    /// Return if the element is in the semigroup.
+    pub fn contains(&self, element: &U) -> bool {
+        self.element_map.contains_key(element)
+    }
+
+    /// Iterate over the elements of the semigroup in short-lex order.
+    pub fn iter(&self) -> std::slice::Iter<'_, U> {
+        self.elements.iter()
+    }
+
+    /// Return if the semigroup was enumerated as a monoid.
+    /// This is the case when the semigroup supplies an identity, which is then the element at position zero and is represented by the empty word.
+    pub fn is_monoid(&self) -> bool {
+        self.is_monoid
+    }
+
+    /// Return the identity if the semigroup was enumerated as a monoid.
+    pub fn identity(&self) -> Option<&U> {
+        if self.is_monoid {
+            self.elements.first()
+        } else {
+            None
+        }
+    }
+
+    /// Return the generators used for the enumeration.
+    /// Duplicate generators and the identity are not included.
+    pub fn generators(&self) -> &[U] {
+        &self.generators[..]
+    }
+
+    /// Return the number of generators used for the enumeration.
+    pub fn number_of_generators(&self) -> usize {
+        self.generators.len()
+    }
+
+    /// Return the position of the generator, or None if the index is out of range.
+    pub fn generator_position(&self, generator: usize) -> Option<usize> {
+        // The generators come straight after the identity, if there is one.
+        if generator < self.generators.len() {
+            Some(generator + self.is_monoid as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Return the rewrite rules found during the enumeration.
+    /// Each letter in the words is an index into the generators.
+    pub fn rules(&self) -> &[(Word<usize>, Word<usize>)] {
+        &self.rewrite_rules[..]
+    }
+
+    /// Return the position of `element * generator`, where `generator` is an index into the generators.
+    /// None is returned if either index is out of range.
+    pub fn right_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        cayley_lookup(&self.right_cayley_graph, element, generator)
+    }
+
+    /// Return the position of `generator * element`, where `generator` is an index into the generators.
+    /// None is returned if either index is out of range.
+    pub fn left_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        cayley_lookup(&self.left_cayley_graph, element, generator)
+    }
+
+    /// Return the short-lex least word over the generators that represents the element at the given position.
+    /// The identity of a monoid is represented by the empty word. None is returned if the position is out of range.
+    pub fn normal_form(&self, index: usize) -> Option<Word<usize>> {
+        if index >= self.size() {
+            return None;
+        }
+        // We repeatedly take the first value and move onto the suffix to create our word
+        Some(
+            successors(Some(index), |&cur_pos| self.suffix[cur_pos])
+                .take(self.length[index])
+                .map(|cur_pos| self.first[cur_pos])
+                .collect(),
+        )
+    }
+
+    /// Return the short-lex normal forms of the elements, in the same order as the elements.
+    pub fn normal_forms(&self) -> Vec<Word<usize>> {
+        EnumeratedSemigroup::normal_forms(self)
+    }
+
+    /// Return the short-lex least word over the generators that evaluates to the element,
+    /// or None if the element is not in the semigroup.
+    /// Each letter in the word is an index into the generators, and the identity of a monoid is the empty word.
+    pub fn minimal_factorisation(&self, element: &U) -> Option<Word<usize>> {
+        self.normal_form(self.position(element)?)
+    }
+
+    /// Return the position of the element that a word over the generators evaluates to, using the right Cayley graph.
+    /// None is returned if a letter is not the index of a generator, or the word is empty and the semigroup is not a monoid.
+    pub fn word_to_position(&self, word: &Word<usize>) -> Option<usize> {
+        EnumeratedSemigroup::word_to_position(self, word)
+    }
+
+    /// Return a presentation of the semigroup, with the generators indices as the alphabet,
+    /// along with the normal forms of the elements.
+    /// The relations are the rewrite rules, which are reduced, so none of them follows from the others by rewriting.
+    /// Some relations may still follow from the others in other ways, and these are not removed,
+    /// as checking this needs an enumeration for each relation that may not terminate.
+    pub fn presentation(&self) -> Presentation<usize> {
+        Presentation::from_vec_unchecked(
+            (0..self.generators.len()).collect(),
+            self.rewrite_rules.clone(),
+            self.is_monoid,
+        )
+        .with_normal_forms(self.normal_forms())
+    }
+
+    /// Return if the element at the given position is an idempotent, i.e if x * x = x.
+    /// False is returned if the position is out of range.
+    pub fn is_idempotent(&self, index: usize) -> bool {
+        index < self.size() && self.product_by_reduction(index, index) == index
+    }
+
+    /// Return the idempotents of the semigroup in short-lex order.
+    pub fn idempotents(&self) -> Vec<&U> {
+        (0..self.size())
+            .filter(|&index| self.is_idempotent(index))
+            .map(|index| &self.elements[index])
+            .collect()
+    }
+
+    /// Return the number of idempotents in the semigroup.
+    pub fn number_of_idempotents(&self) -> usize {
+        (0..self.size())
+            .filter(|&index| self.is_idempotent(index))
+            .count()
+    }
+
+    /// Return the position of x * y by following the normal form of y from x in the right Cayley graph.
+    /// This avoids multiplying the elements, which is often more expensive than the length of the word.
+    fn product_by_reduction(&self, x: usize, y: usize) -> usize {
+        successors(Some(y), |&cur_pos| self.suffix[cur_pos])
+            .take(self.length[y])
+            .fold(x, |pos, cur_pos| {
+                // The Cayley graph is complete once the enumeration has finished.
+                self.right_cayley_graph[(pos, self.first[cur_pos])].unwrap()
+            })
+    }
+
+    /// Compute the least congruence of the given kind containing the pairs of elements,
+    /// or return None if any of the elements are not in the semigroup.
+    pub fn congruence(&self, kind: CongruenceKind, pairs: &[(U, U)]) -> Option<Congruence> {
+        let pairs = pairs
+            .iter()
+            .map(|(x, y)| Some((self.position(x)?, self.position(y)?)))
+            .collect::<Option<Vec<_>>>()?;
+        Congruence::new(self, kind, &pairs)
+    }
+
+    /// Compute the least ideal of the given kind containing the elements,
+    /// or return None if any of the elements are not in the semigroup.
+    pub fn ideal(&self, kind: IdealKind, generators: &[U]) -> Option<Ideal> {
+        let generators = generators
+            .iter()
+            .map(|x| self.position(x))
+            .collect::<Option<Vec<_>>>()?;
+        Ideal::new(self, kind, &generators)
+    }
+}
+
+impl<U> EnumeratedSemigroup for FroidurePinResult<U>
+where
+    U: SemigroupElement + Hash,
+{
+    fn size(&self) -> usize {
+        FroidurePinResult::size(self)
+    }
+
+    fn is_monoid(&self) -> bool {
+        FroidurePinResult::is_monoid(self)
+    }
+
+    fn number_of_generators(&self) -> usize {
+        FroidurePinResult::number_of_generators(self)
+    }
+
+    fn generator_position(&self, generator: usize) -> Option<usize> {
+        FroidurePinResult::generator_position(self, generator)
+    }
+
+    fn right_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        FroidurePinResult::right_cayley(self, element, generator)
+    }
+
+    fn left_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        FroidurePinResult::left_cayley(self, element, generator)
+    }
+
+    fn normal_form(&self, index: usize) -> Option<Word<usize>> {
+        FroidurePinResult::normal_form(self, index)
+    }
+}
+
+impl<'a, U> IntoIterator for &'a FroidurePinResult<U>
+where
+    U: SemigroupElement + Hash,
+{
+    type Item = &'a U;
+    type IntoIter = std::slice::Iter<'a, U>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Serialized form of the result, without the element map as its keys are not strings.
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use std::{convert::TryFrom, hash::Hash};
+
+    use snafu::Snafu;
+
+    use super::{CayleyGraphType, FroidurePinResult};
+    use crate::{element::SemigroupElement, semigroup::word::Word, DetHashMap};
+
+    #[derive(serde::Deserialize)]
+    pub(super) struct ResultData<U> {
+        generators: Vec<U>,
+        is_monoid: bool,
+        elements: Vec<U>,
+        rewrite_rules: Vec<(Word<usize>, Word<usize>)>,
+        left_cayley_graph: CayleyGraphType,
+        right_cayley_graph: CayleyGraphType,
+        first: Vec<usize>,
+        suffix: Vec<Option<usize>>,
+        length: Vec<usize>,
+    }
+
+    /// Ways a serialized result can be inconsistent, which would otherwise cause a panic when it is used.
+    #[derive(Debug, Snafu)]
+    pub(super) enum ResultDataError {
+        #[snafu(display("Element {} appears more than once", position))]
+        DuplicateElement { position: usize },
+        #[snafu(display("Generator {} is not at position {}", generator, position))]
+        MisplacedGenerator { generator: usize, position: usize },
+        #[snafu(display("Expected {} values in {} but found {}", expected, field, found))]
+        MismatchingLength {
+            field: &'static str,
+            expected: usize,
+            found: usize,
+        },
+        #[snafu(display(
+            "Expected {} by {} {} but found {} by {}",
+            expected_rows,
+            expected_cols,
+            field,
+            rows,
+            cols
+        ))]
+        MismatchingCayleyGraph {
+            field: &'static str,
+            expected_rows: usize,
+            expected_cols: usize,
+            rows: usize,
+            cols: usize,
+        },
+        #[snafu(display("Invalid value in {} at index {}", field, index))]
+        InvalidValue { field: &'static str, index: usize },
+    }
+
+    impl<U> TryFrom<ResultData<U>> for FroidurePinResult<U>
+    where
+        U: SemigroupElement + Hash,
+    {
+        type Error = ResultDataError;
+
+        fn try_from(data: ResultData<U>) -> Result<Self, Self::Error> {
+            let size = data.elements.len();
+            let n_gens = data.generators.len();
+            let mut element_map = DetHashMap::default();
+            for (position, x) in data.elements.iter().enumerate() {
+                if element_map.insert(x.clone(), position).is_some() {
+                    return Err(ResultDataError::DuplicateElement { position });
+                }
+            }
+            // The identity is at position zero of a monoid, followed by the generators.
+            if data.is_monoid && size == 0 {
+                return Err(ResultDataError::MismatchingLength {
+                    field: "elements",
+                    expected: 1,
+                    found: 0,
+                });
+            }
+            for (generator, x) in data.generators.iter().enumerate() {
+                let position = generator + data.is_monoid as usize;
+                if element_map.get(x) != Some(&position) {
+                    return Err(ResultDataError::MisplacedGenerator {
+                        generator,
+                        position,
+                    });
+                }
+            }
+            let lengths = [
+                ("first", data.first.len()),
+                ("suffix", data.suffix.len()),
+                ("length", data.length.len()),
+            ];
+            for &(field, found) in lengths.iter() {
+                if found != size {
+                    return Err(ResultDataError::MismatchingLength {
+                        field,
+                        expected: size,
+                        found,
+                    });
+                }
+            }
+            let graphs = [
+                ("left_cayley_graph", &data.left_cayley_graph),
+                ("right_cayley_graph", &data.right_cayley_graph),
+            ];
+            for &(field, graph) in graphs.iter() {
+                if graph.n_rows() != size || graph.n_cols() != n_gens {
+                    return Err(ResultDataError::MismatchingCayleyGraph {
+                        field,
+                        expected_rows: size,
+                        expected_cols: n_gens,
+                        rows: graph.n_rows(),
+                        cols: graph.n_cols(),
+                    });
+                }
+                // The Cayley graphs are complete once the enumeration has finished.
+                for index in 0..size {
+                    if !graph
+                        .get_row(index)
+                        .iter()
+                        .all(|x| x.is_some_and(|x| x < size))
+                    {
+                        return Err(ResultDataError::InvalidValue { field, index });
+                    }
+                }
+            }
+            for index in 0..size {
+                // The empty word has no first letter or suffix, so only the identity may have length zero.
+                let is_identity = data.is_monoid && index == 0;
+                if (data.length[index] == 0) != is_identity {
+                    return Err(ResultDataError::InvalidValue {
+                        field: "length",
+                        index,
+                    });
+                }
+                if !is_identity && data.first[index] >= n_gens {
+                    return Err(ResultDataError::InvalidValue {
+                        field: "first",
+                        index,
+                    });
+                }
+                // The suffix is one letter shorter, so following suffixes always reaches a generator.
+                let suffix_length = data.suffix[index].map_or(Some(1), |suffix| {
+                    data.length.get(suffix).map(|length| length + 1)
+                });
+                if !is_identity && suffix_length != Some(data.length[index]) {
+                    return Err(ResultDataError::InvalidValue {
+                        field: "suffix",
+                        index,
+                    });
+                }
+            }
+            for (index, (lhs, rhs)) in data.rewrite_rules.iter().enumerate() {
+                if lhs
+                    .as_vec()
+                    .iter()
+                    .chain(rhs.as_vec())
+                    .any(|&a| a >= n_gens)
+                {
+                    return Err(ResultDataError::InvalidValue {
+                        field: "rewrite_rules",
+                        index,
+                    });
+                }
+            }
+            Ok(Self {
+                generators: data.generators,
+                is_monoid: data.is_monoid,
+                elements: data.elements,
+                element_map,
+                rewrite_rules: data.rewrite_rules,
+                left_cayley_graph: data.left_cayley_graph,
+                right_cayley_graph: data.right_cayley_graph,
+                first: data.first,
+                suffix: data.suffix,
+                length: data.length,
+            })
+        }
+    }
 }
 
 pub trait FroidurePinBuilder<T>
@@ -37,8 +614,218 @@ where
     fn build(self) -> FroidurePinResult<T>;
 }
 
-/// Macro for testing multiple implementations.
+/// Helpers for checking that every implementation gives the same result.
+#[cfg(test)]
+mod cross_check {
+    use std::{fmt::Debug, hash::Hash};
+
+    use super::{
+        froidure_pin_impl::FroidurePin, parallel::FroidurePinParallel, simple::FroidurePinSimple,
+        FroidurePinBuilder, FroidurePinResult,
+    };
+    use crate::{
+        element::{partial_perm::PartialPerm, transformation::Transformation, SemigroupElement},
+        semigroup::{
+            impls::{partial_perm::PartialPermSemigroup, transformation::TransformationSemigroup},
+            Semigroup,
+        },
+    };
+
+    /// Semigroup that does not supply an identity.
+    pub(super) struct NoIdentitySemigroup<T>(pub(super) Vec<T>);
+
+    impl<T> std::fmt::Display for NoIdentitySemigroup<T>
+    where
+        T: Debug,
+    {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+
+    impl<T> Semigroup<T> for NoIdentitySemigroup<T>
+    where
+        T: SemigroupElement + Debug,
+    {
+        fn generators(&self) -> &[T] {
+            &self.0[..]
+        }
+    }
+
+    /// Assert that two results are identical, including the order of the elements and rules.
+    pub(super) fn assert_same_result<T>(a: &FroidurePinResult<T>, b: &FroidurePinResult<T>)
+    where
+        T: SemigroupElement + Hash + Debug,
+    {
+        assert_eq!(a.generators, b.generators);
+        assert_eq!(a.is_monoid, b.is_monoid);
+        assert_eq!(a.elements, b.elements);
+        assert_eq!(a.element_map, b.element_map);
+        assert_eq!(a.rewrite_rules, b.rewrite_rules);
+        assert_eq!(a.right_cayley_graph, b.right_cayley_graph);
+        assert_eq!(a.left_cayley_graph, b.left_cayley_graph);
+        assert_eq!(a.first, b.first);
+        assert_eq!(a.suffix, b.suffix);
+        assert_eq!(a.length, b.length);
+    }
+
+    /// Build the result using the given implementation, checking that it is the same as the result of `FroidurePinSimple`.
+    pub(super) fn cross_checked_build<B, T, S>(semigroup: &S) -> FroidurePinResult<T>
+    where
+        B: FroidurePinBuilder<T>,
+        T: SemigroupElement + Hash + Debug,
+        S: Semigroup<T>,
+    {
+        let res = B::new(semigroup).build();
+        assert_same_result(&res, &FroidurePinSimple::new(semigroup).build());
+        res
+    }
+
+    /// Check that every implementation gives the same result.
+    fn assert_implementations_agree<T, S>(semigroup: &S)
+    where
+        T: SemigroupElement + Hash + Debug + Send + Sync,
+        S: Semigroup<T>,
+    {
+        let res = FroidurePin::new(semigroup).build();
+        assert_same_result(&res, &FroidurePinSimple::new(semigroup).build());
+        let parallel = FroidurePinParallel::new(semigroup).with_threads(3);
+        assert_same_result(&res, &parallel.build());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(4, vec![1, 0, 2, 3]).unwrap(),
+            Transformation::from_vec(4, vec![1, 2, 3, 0]).unwrap(),
+            Transformation::from_vec(4, vec![0, 0, 2, 3]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let json = serde_json::to_string(&res).unwrap();
+        let loaded: FroidurePinResult<Transformation> = serde_json::from_str(&json).unwrap();
+        assert_same_result(&loaded, &res);
+        // Inconsistent results are rejected rather than panicking when they are used.
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let load = |edit: &dyn Fn(&mut serde_json::Value)| {
+            let mut value = value.clone();
+            edit(&mut value);
+            serde_json::from_value::<FroidurePinResult<Transformation>>(value)
+        };
+        assert!(load(&|_| {}).is_ok());
+        assert!(load(&|v| {
+            v["first"].as_array_mut().unwrap().pop();
+        })
+        .is_err());
+        assert!(load(&|v| v["right_cayley_graph"]["n_rows"] = 1.into()).is_err());
+        assert!(load(&|v| v["left_cayley_graph"]["vec"][5] = 256.into()).is_err());
+        assert!(load(&|v| v["left_cayley_graph"]["vec"][5] = serde_json::Value::Null).is_err());
+        assert!(load(&|v| v["suffix"][100] = 255.into()).is_err());
+        assert!(load(&|v| v["elements"][5] = v["elements"][4].clone()).is_err());
+        assert!(load(&|v| v["generators"].as_array_mut().unwrap().swap(0, 1)).is_err());
+        assert!(load(&|v| v["rewrite_rules"][0][0][0] = 3.into()).is_err());
+    }
+
+    /// Xorshift generator so that the random tests are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: usize) -> usize {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            (self.0 % n as u64) as usize
+        }
+    }
+
+    #[test]
+    fn random_transformation_semigroups() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+        for _ in 0..50 {
+            let degree = 1 + rng.below(5);
+            let gens: Vec<Transformation> = (0..1 + rng.below(4))
+                .map(|_| {
+                    let image = (0..degree).map(|_| rng.below(degree)).collect();
+                    Transformation::from_vec(degree, image).unwrap()
+                })
+                .collect();
+            let s = TransformationSemigroup::new(&gens).unwrap();
+            assert_implementations_agree(&s);
+            let s = NoIdentitySemigroup(gens);
+            assert_implementations_agree(&s);
+        }
+    }
+
+    #[test]
+    fn random_add_generators() {
+        let mut rng = Rng(0x0123_4567_89ab_cdef);
+        for _ in 0..50 {
+            let degree = 1 + rng.below(5);
+            let mut gens: Vec<Transformation> = (0..2 + rng.below(4))
+                .map(|_| {
+                    let image = (0..degree).map(|_| rng.below(degree)).collect();
+                    Transformation::from_vec(degree, image).unwrap()
+                })
+                .collect();
+            // A product of the first generators, which is already an element when it is added.
+            gens.push(gens[0].multiply(&gens[0]).unwrap());
+            for &is_monoid in [true, false].iter() {
+                let first = 1 + rng.below(gens.len() - 1);
+                let (expected, mut fp) = if is_monoid {
+                    let s = TransformationSemigroup::new(&gens[..first]).unwrap();
+                    let all = TransformationSemigroup::new(&gens).unwrap();
+                    (FroidurePin::new(&all).build(), FroidurePin::new(&s))
+                } else {
+                    let s = NoIdentitySemigroup(gens[..first].to_vec());
+                    let all = NoIdentitySemigroup(gens.clone());
+                    (FroidurePin::new(&all).build(), FroidurePin::new(&s))
+                };
+                // Add the rest of the generators in batches, stopping the enumeration at random points in between.
+                let mut added = first;
+                while added < gens.len() {
+                    fp.enumerate(rng.below(2 * fp.current_size() + 2));
+                    let next = added + 1 + rng.below(gens.len() - added);
+                    fp.add_generators(&gens[added..next]);
+                    added = next;
+                }
+                assert_same_result(&fp.build(), &expected);
+            }
+        }
+    }
 
+    #[test]
+    fn random_partial_perm_semigroups() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..50 {
+            let degree = 1 + rng.below(4);
+            let gens: Vec<PartialPerm> = (0..1 + rng.below(3))
+                .map(|_| {
+                    // Take a random permutation and remove some points from the domain.
+                    let mut image: Vec<usize> = (0..degree).collect();
+                    for i in (1..degree).rev() {
+                        image.swap(i, rng.below(i + 1));
+                    }
+                    let image = image
+                        .into_iter()
+                        .map(|x| if rng.below(4) == 0 { None } else { Some(x) })
+                        .collect();
+                    PartialPerm::from_vec(degree, image).unwrap()
+                })
+                .collect();
+            let s = PartialPermSemigroup::new(&gens).unwrap();
+            assert_implementations_agree(&s);
+            let s = NoIdentitySemigroup(gens);
+            assert_implementations_agree(&s);
+        }
+    }
+}
+
+#[cfg(test)]
+use cross_check::{cross_checked_build, NoIdentitySemigroup};
+
+/// Macro for testing multiple implementations.
+/// Every test also checks the result is identical to that of `FroidurePinSimple`.
 macro_rules! froidure_pin_test {
     ($test_impl:ty, $name:ident) => {
         #[cfg(test)]
@@ -55,10 +842,9 @@ macro_rules! froidure_pin_test {
                     Transformation::from_vec(3, vec![0, 1, 2]).unwrap()
                 ])
                 .unwrap();
-                let fp = <$test_impl>::new(&s);
-                let res = fp.build();
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
                 dbg!(&res);
-                assert!(res.elements.len() == 1);
+                assert!(res.size() == 1);
             }
 
             #[test]
@@ -68,9 +854,8 @@ macro_rules! froidure_pin_test {
                     Transformation::from_vec(5, vec![0, 2, 3, 4, 1]).unwrap(),
                 ])
                 .unwrap();
-                let fp = <$test_impl>::new(&s);
-                let res = fp.build();
-                assert!(res.elements.len() == 120);
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(res.size() == 120);
             }
 
             #[test]
@@ -81,9 +866,8 @@ macro_rules! froidure_pin_test {
                     Transformation::from_vec(5, vec![1, 1, 2, 3, 4]).unwrap(),
                 ])
                 .unwrap();
-                let fp = <$test_impl>::new(&s);
-                let res = fp.build();
-                assert!(res.elements.len() == 3125);
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(res.size() == 3125);
             }
 
             #[test]
@@ -93,9 +877,142 @@ macro_rules! froidure_pin_test {
                     Transformation::from_vec(6, vec![4, 2, 3, 3, 5, 5]).unwrap(),
                 ])
                 .unwrap();
-                let fp = <$test_impl>::new(&s);
-                let res = fp.build();
-                assert!(res.elements.len() == 7);
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(res.size() == 7);
+            }
+
+            #[test]
+            fn paper_example_semigroup() {
+                let s = NoIdentitySemigroup(vec![
+                    Transformation::from_vec(6, vec![1, 1, 3, 3, 4, 5]).unwrap(),
+                    Transformation::from_vec(6, vec![4, 2, 3, 3, 5, 5]).unwrap(),
+                ]);
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(!res.is_monoid());
+                assert!(res.identity().is_none());
+                assert!(res.size() == 6);
+                assert!(!res.contains(&Transformation::id(6)));
+                // Generators are at the start when there is no identity
+                assert_eq!(res.position(&s.0[0]), Some(0));
+                assert_eq!(res.position(&s.0[1]), Some(1));
+            }
+
+            #[test]
+            fn symmetric_group_5_semigroup() {
+                let s = NoIdentitySemigroup(vec![
+                    Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+                    Transformation::from_vec(5, vec![0, 2, 3, 4, 1]).unwrap(),
+                ]);
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(!res.is_monoid());
+                assert!(res.size() == 120);
+                // The identity is still found as a product of the generators
+                assert!(res.position(&Transformation::id(5)).unwrap() > 1);
+            }
+
+            #[test]
+            fn identity_generator_semigroup() {
+                // The identity is kept as a generator when the semigroup does not supply one
+                let s = NoIdentitySemigroup(vec![Transformation::id(3)]);
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(res.size() == 1);
+                assert!(res.generators().len() == 1);
+                assert_eq!(res.right_cayley(0, 0), Some(0));
+                // Empty semigroup
+                let s = NoIdentitySemigroup(vec![]);
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(res.size() == 0);
+            }
+
+            #[test]
+            fn query_api() {
+                let f = Transformation::from_vec(3, vec![1, 0, 2]).unwrap();
+                let g = Transformation::from_vec(3, vec![0, 0, 2]).unwrap();
+                let s = TransformationSemigroup::new(&[f.clone(), g.clone()]).unwrap();
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert!(res.is_monoid());
+                assert_eq!(res.identity(), Some(&Transformation::id(3)));
+                assert_eq!(res.generators(), &[f.clone(), g.clone()]);
+                // Elements are in short-lex order, starting with the identity and then the generators.
+                assert_eq!(res.element(0), Some(&Transformation::id(3)));
+                assert_eq!(res.position(&f), Some(1));
+                assert_eq!(res.position(&g), Some(2));
+                assert!(res.element(res.size()).is_none());
+                // Positions and elements agree
+                for (i, x) in res.iter().enumerate() {
+                    assert_eq!(res.position(x), Some(i));
+                    assert!(res.contains(x));
+                }
+                assert!(!res.contains(&Transformation::from_vec(3, vec![2, 2, 2]).unwrap()));
+                // Check the Cayley graphs agree with multiplication.
+                for i in 0..res.size() {
+                    for (j, gen) in res.generators().iter().enumerate() {
+                        let x = res.element(i).unwrap();
+                        let right = res.right_cayley(i, j).unwrap();
+                        assert_eq!(res.element(right).unwrap(), &x.multiply(gen).unwrap());
+                        let left = res.left_cayley(i, j).unwrap();
+                        assert_eq!(res.element(left).unwrap(), &gen.multiply(x).unwrap());
+                    }
+                }
+                assert!(res.right_cayley(0, 2).is_none());
+            }
+
+            #[test]
+            fn idempotents() {
+                let s = TransformationSemigroup::new(&[
+                    Transformation::from_vec(4, vec![1, 0, 2, 3]).unwrap(),
+                    Transformation::from_vec(4, vec![1, 2, 3, 0]).unwrap(),
+                    Transformation::from_vec(4, vec![0, 0, 2, 3]).unwrap(),
+                ])
+                .unwrap();
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert_eq!(res.number_of_idempotents(), 41);
+                let expected: Vec<_> = res
+                    .iter()
+                    .filter(|x| &x.multiply(x).unwrap() == *x)
+                    .collect();
+                assert_eq!(res.idempotents(), expected);
+                assert!(res.is_idempotent(0));
+                assert!(!res.is_idempotent(res.size()));
+                // The identity is the only idempotent in a group.
+                let s = TransformationSemigroup::new(&[
+                    Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+                    Transformation::from_vec(5, vec![0, 2, 3, 4, 1]).unwrap(),
+                ])
+                .unwrap();
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert_eq!(res.idempotents(), vec![&Transformation::id(5)]);
+            }
+
+            #[test]
+            fn factorisation() {
+                let s = TransformationSemigroup::new(&[
+                    Transformation::from_vec(4, vec![1, 0, 2, 3]).unwrap(),
+                    Transformation::from_vec(4, vec![1, 2, 3, 0]).unwrap(),
+                    Transformation::from_vec(4, vec![0, 0, 2, 3]).unwrap(),
+                ])
+                .unwrap();
+                let res = cross_checked_build::<$test_impl, _, _>(&s);
+                assert_eq!(res.size(), 256);
+                for (i, x) in res.iter().enumerate() {
+                    let word = res.minimal_factorisation(x).unwrap();
+                    assert_eq!(res.normal_form(i), Some(word.clone()));
+                    assert_eq!(res.word_to_position(&word), Some(i));
+                    // Evaluating the word by multiplying should give back the element.
+                    let product = word.as_vec().iter().fold(Transformation::id(4), |acc, &a| {
+                        acc.multiply(&res.generators()[a]).unwrap()
+                    });
+                    assert_eq!(&product, x);
+                }
+                let word = vec![2, 1, 1].into_iter().collect();
+                let x = res.element(res.word_to_position(&word).unwrap()).unwrap();
+                assert!(res.minimal_factorisation(x).unwrap() <= word);
+                assert!(res
+                    .word_to_position(&vec![0, 3].into_iter().collect())
+                    .is_none());
+                let y = Transformation::from_vec(4, vec![0, 1, 2, 2]).unwrap();
+                assert!(res.minimal_factorisation(&y).is_some());
+                assert!(res.minimal_factorisation(&Transformation::id(5)).is_none());
             }
         }
 
@@ -117,3 +1034,4 @@ froidure_pin_test!(
     froidure_pin_test
 );
 froidure_pin_test!(simple::FroidurePinSimple<Transformation>, simple_test);
+froidure_pin_test!(parallel::FroidurePinParallel<Transformation>, parallel_test);
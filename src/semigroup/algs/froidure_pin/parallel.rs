@@ -0,0 +1,126 @@
+use std::{fmt::Debug, hash::Hash, num::NonZeroUsize, thread};
+
+use super::{froidure_pin_impl::FroidurePin, FroidurePinBuilder, FroidurePinResult};
+use crate::{element::SemigroupElement, semigroup::Semigroup};
+
+/// The fewest products each thread is given, so that small layers are not split between threads.
+const MIN_PRODUCTS_PER_THREAD: usize = 64;
+
+/// Multithreaded version of Froidure-Pin.
+///
+/// The products needed for each layer of words of the same length are computed in parallel,
+/// and are then added to the semigroup in the same order as `FroidurePin`, so the result is identical.
+#[derive(Debug)]
+pub struct FroidurePinParallel<T>
+where
+    T: SemigroupElement + Hash,
+{
+    inner: FroidurePin<T>,
+    n_threads: usize,
+}
+
+impl<T> FroidurePinParallel<T>
+where
+    T: SemigroupElement + Hash + Debug + Send + Sync,
+{
+    /// Set the number of threads to use, which is the available parallelism by default.
+    /// This will panic if the number of threads is zero.
+    pub fn with_threads(mut self, n_threads: usize) -> Self {
+        assert!(n_threads > 0, "Number of threads must be positive");
+        self.n_threads = n_threads;
+        self
+    }
+
+    /// Return the number of threads used for the enumeration.
+    pub fn n_threads(&self) -> usize {
+        self.n_threads
+    }
+
+    fn run(&mut self) {
+        let n_threads = self.n_threads;
+        while !self.inner.is_finished() {
+            self.inner
+                .precompute_layer(|elements, pairs| multiply_all(elements, pairs, n_threads));
+            let next_length = self.inner.current_word_length() + 1;
+            self.inner.run_until_word_length(next_length);
+        }
+    }
+}
+
+/// Multiply the elements at each pair of positions, splitting the work between the threads.
+fn multiply_all<T>(elements: &[T], pairs: &[(usize, usize)], n_threads: usize) -> Vec<T>
+where
+    T: SemigroupElement + Send + Sync,
+{
+    let chunk_size = pairs.len().div_ceil(n_threads).max(MIN_PRODUCTS_PER_THREAD);
+    let multiply_chunk = |chunk: &[(usize, usize)]| -> Vec<T> {
+        chunk
+            .iter()
+            .map(|&(x, y)| elements[x].multiply(&elements[y]))
+            .collect()
+    };
+    if pairs.len() <= chunk_size {
+        return multiply_chunk(pairs);
+    }
+    thread::scope(|scope| {
+        let handles: Vec<_> = pairs
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || multiply_chunk(chunk)))
+            .collect();
+        // Join in order, so the products are in the same order as the pairs.
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("Multiplication thread panicked"))
+            .collect()
+    })
+}
+
+impl<T> FroidurePinBuilder<T> for FroidurePinParallel<T>
+where
+    T: SemigroupElement + Hash + Debug + Send + Sync,
+{
+    fn new<U>(semigroup: &U) -> Self
+    where
+        U: Semigroup<T>,
+    {
+        Self {
+            inner: FroidurePin::new(semigroup),
+            n_threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    fn build(mut self) -> FroidurePinResult<T> {
+        self.run();
+        self.inner.build()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::FroidurePinParallel;
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{
+                cross_check::assert_same_result, froidure_pin_impl::FroidurePin, FroidurePinBuilder,
+            },
+            impls::transformation::TransformationSemigroup,
+        },
+    };
+
+    #[test]
+    fn thread_counts_agree() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(5, vec![1, 0, 2, 3, 4]).unwrap(),
+            Transformation::from_vec(5, vec![1, 2, 3, 4, 0]).unwrap(),
+            Transformation::from_vec(5, vec![1, 1, 2, 3, 4]).unwrap(),
+        ])
+        .unwrap();
+        let expected = FroidurePin::new(&s).build();
+        for n_threads in 1..=4 {
+            let fp = FroidurePinParallel::new(&s).with_threads(n_threads);
+            assert_eq!(fp.n_threads(), n_threads);
+            assert_same_result(&fp.build(), &expected);
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use super::{CayleyGraphType, FroidurePinBuilder, FroidurePinResult};
 use itertools::Itertools;
-use std::hash::Hash;
+use std::{
+    hash::Hash,
+    iter::{repeat, successors},
+};
 
 use crate::{
     element::SemigroupElement,
@@ -14,6 +17,10 @@ where
     T: SemigroupElement + Hash,
 {
     generators: Vec<T>,
+    // If the semigroup supplied an identity, in which case it is stored at position zero.
+    is_monoid: bool,
+    // At index i store the position of generator i in the elements
+    generator_positions: Vec<usize>,
     // Elements sorted in military order
     elements: Vec<T>,
     // Map of elements to position in gens
@@ -23,6 +30,15 @@ where
     // The left and right Cayley graphs, which index into the elements.
     left_cayley_graph: CayleyGraphType,
     right_cayley_graph: CayleyGraphType,
+    // Store if a given element is reduced, i.e if it was new when we first encountered it
+    reduced: Vec2<bool>,
+    // At index i store the index of the first generator in the word representing elements[i]
+    first: Vec<usize>,
+    // At index i store the index of the element represented by the word for elements[i] without its first generator
+    // This is None if the suffix is the empty word.
+    suffix: Vec<Option<usize>>,
+    // Index i stores the length of the word representing elements[i]
+    length: Vec<usize>,
 }
 
 impl<T> FroidurePinSimple<T>
@@ -33,58 +49,91 @@ where
     where
         U: Semigroup<T>,
     {
+        let identity = gens.id();
         // Filter out duplicate generators and the identity
         let generators: Vec<T> = gens
             .generators()
             .iter()
             .unique()
-            .filter(|s| !s.is_id())
+            .filter(|s| Some(*s) != identity.as_ref())
             .cloned()
             .collect();
-        // Initial elements are just the generators
-        let mut elements = generators.clone();
-        // Insert identity into position zero.
-        elements.insert(0, gens.id().unwrap());
+        let is_monoid = identity.is_some();
+        // Initial elements are the identity if we have one, and then the generators
+        let elements: Vec<T> = identity
+            .into_iter()
+            .chain(generators.iter().cloned())
+            .collect();
+        let offset = elements.len() - generators.len();
+        let generator_positions: Vec<usize> = (offset..elements.len()).collect();
         let mut element_map = DetHashMap::default();
         // Add to element map
         for (idx, elem) in elements.iter().enumerate() {
             element_map.insert(elem.clone(), idx);
         }
         let rewrite_rules = Vec::new();
-        // 2d arrays for the Cayley graphs
-        let mut left_cayley_graph = Vec2::new(elements.len(), elements.len());
-        let mut right_cayley_graph = Vec2::new(elements.len(), elements.len());
+        // The identity is the empty word, and each generator is a word of length one.
+        let first = (0..offset).chain(0..generators.len()).collect();
+        let suffix = vec![None; elements.len()];
+        let length = repeat(0)
+            .take(offset)
+            .chain(repeat(1).take(generators.len()))
+            .collect();
+        // 2d arrays for the Cayley graphs and if a word is reduced.
+        let mut left_cayley_graph = Vec2::new(elements.len(), generators.len());
+        let mut right_cayley_graph = Vec2::new(elements.len(), generators.len());
         // Initialise identity for the graphs
-        for i in 1..=generators.len() {
-            left_cayley_graph[(i, 0)] = Some(i);
-            left_cayley_graph[(0, i)] = Some(i);
-            right_cayley_graph[(i, 0)] = Some(i);
-            right_cayley_graph[(0, i)] = Some(i);
+        if is_monoid {
+            for (i, &pos) in generator_positions.iter().enumerate() {
+                left_cayley_graph[(0, i)] = Some(pos);
+                right_cayley_graph[(0, i)] = Some(pos);
+            }
         }
+        let reduced = Vec2::new(elements.len(), generators.len());
         FroidurePinSimple {
             generators,
+            is_monoid,
+            generator_positions,
             elements,
             element_map,
             rewrite_rules,
             left_cayley_graph,
             right_cayley_graph,
+            reduced,
+            first,
+            suffix,
+            length,
         }
     }
 
+    // Convert an index into a word of the generators, where each letter is an index into the generators.
+    fn pos_to_word(&self, pos: usize) -> Word<usize> {
+        // We repeatedly take the first value and move onto the suffix to create our word
+        successors(Some(pos), |&cur_pos| self.suffix[cur_pos])
+            .take(self.length[pos])
+            .map(|cur_pos| self.first[cur_pos])
+            .collect()
+    }
+
     fn run(&mut self) {
-        // If we only have the identity then we have no work to do.
-        if self.elements.len() == 1 {
-            return;
-        }
-        let mut u = 1;
-        loop {
-            for gen in 1..=self.generators.len() {
-                let product = self.elements[u].multiply(&self.elements[gen]);
+        // Start from the first generator, as the identity has already been handled.
+        let start = if self.is_monoid { 1 } else { 0 };
+        let mut u = start;
+        // End if u has no successors
+        while u < self.elements.len() {
+            for gen in 0..self.generators.len() {
+                let product =
+                    self.elements[u].multiply(&self.elements[self.generator_positions[gen]]);
                 // If we find a new element
                 match self.element_map.get(&product) {
                     // Element has already been found.
                     Some(&idx) => {
-                        //TODO add new rule
+                        // Only add a rule if the suffix multiplied by gen is reduced, as otherwise the rule follows from a shorter one.
+                        if self.suffix[u].map_or(true, |suffix| self.reduced[(suffix, gen)]) {
+                            let lhs = self.pos_to_word(u).append(&gen);
+                            let rhs = self.pos_to_word(idx);
+                            self.rewrite_rules.push((lhs, rhs));
+                        }
                         self.right_cayley_graph[(u, gen)] = Some(idx);
                     }
                     // We've found a new element
@@ -92,6 +141,18 @@ where
                         let new_pos = self.elements.len();
                         self.element_map.insert(product.clone(), new_pos);
                         self.elements.push(product);
+                        // The suffix has already been multiplied by gen, as it is shorter than u.
+                        let suffix = match self.suffix[u] {
+                            Some(suffix) => self.right_cayley_graph[(suffix, gen)]
+                                .expect("Should already be computed"),
+                            None => self.generator_positions[gen],
+                        };
+                        self.first.push(self.first[u]);
+                        self.suffix.push(Some(suffix));
+                        self.length.push(self.length[u] + 1);
+                        // Update reduced table
+                        self.reduced.add_row();
+                        self.reduced[(u, gen)] = true;
                         // Need a new row in the cayley graphs
                         self.right_cayley_graph.add_row();
                         self.left_cayley_graph.add_row();
@@ -100,9 +161,13 @@ where
                 }
             }
             u += 1;
-            // End if u has no successors
-            if u == self.elements.len() {
-                break;
+        }
+        // Now all elements are known we can fill in the left Cayley graph
+        for u in start..self.elements.len() {
+            for gen in 0..self.generators.len() {
+                let product =
+                    self.elements[self.generator_positions[gen]].multiply(&self.elements[u]);
+                self.left_cayley_graph[(u, gen)] = self.element_map.get(&product).copied();
             }
         }
     }
@@ -124,11 +189,15 @@ where
         self.run();
         FroidurePinResult {
             generators: self.generators,
+            is_monoid: self.is_monoid,
             elements: self.elements,
             element_map: self.element_map,
             rewrite_rules: self.rewrite_rules,
             left_cayley_graph: self.left_cayley_graph,
             right_cayley_graph: self.right_cayley_graph,
+            first: self.first,
+            suffix: self.suffix,
+            length: self.length,
         }
     }
 }
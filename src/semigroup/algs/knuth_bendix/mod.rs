@@ -0,0 +1,546 @@
+use std::{collections::VecDeque, fmt::Display, hash::Hash};
+
+use snafu::Snafu;
+
+use crate::{
+    semigroup::{presentation::Presentation, word::Word},
+    utils::vec2::Vec2,
+    DetHashMap,
+};
+
+/// A rewriting rule, with words given by the positions of their symbols in the sorted alphabet.
+type Rule = (Vec<usize>, Vec<usize>);
+
+/// Knuth-Bendix completion of a finite presentation, using the short-lex order on words.
+///
+/// Completion may not terminate, even if the semigroup is finite, so a limit on the number of rules can be set.
+/// ```
+/// use yatyat::semigroup::{algs::knuth_bendix::KnuthBendix, presentation::Presentation, word::Word};
+///
+/// let word = |s: &str| s.chars().collect::<Word<char>>();
+/// // The symmetric group of degree 3.
+/// let p = Presentation::new(
+///     vec!['a', 'b'],
+///     vec![(word("aa"), word("")), (word("bbb"), word("")), (word("abab"), word(""))],
+///     true,
+/// )
+/// .unwrap();
+/// let rws = KnuthBendix::new(&p).complete().unwrap();
+/// assert_eq!(rws.size(), Some(6));
+/// assert!(rws.equal(&word("ba"), &word("abb")).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct KnuthBendix<A>
+where
+    A: Ord + Clone,
+{
+    // The alphabet in increasing order
+    alphabet: Vec<A>,
+    contains_empty_word: bool,
+    relations: Vec<Rule>,
+    max_rules: Option<usize>,
+    // Rules that have been removed during completion are left as None, so positions stay the same.
+    rules: Vec<Option<Rule>>,
+    // Map from the left hand side of each rule to its position
+    index: DetHashMap<Vec<usize>, usize>,
+    // An upper bound on the length of the left hand sides
+    max_lhs_len: usize,
+    pending: Vec<Rule>,
+}
+
+impl<A> KnuthBendix<A>
+where
+    A: Ord + Clone + Hash + Display,
+{
+    /// Create the completion of the given presentation.
+    pub fn new(presentation: &Presentation<A>) -> Self {
+        let mut alphabet = presentation.alphabet().to_vec();
+        alphabet.sort();
+        // The presentation has already checked all of the symbols are in the alphabet.
+        let indices = |word: &Word<A>| -> Vec<usize> {
+            word.as_vec()
+                .iter()
+                .map(|a| alphabet.binary_search(a).unwrap())
+                .collect()
+        };
+        let relations = presentation
+            .relations()
+            .iter()
+            .map(|(lhs, rhs)| (indices(lhs), indices(rhs)))
+            .collect();
+        Self {
+            alphabet,
+            contains_empty_word: presentation.contains_empty_word(),
+            relations,
+            max_rules: None,
+            rules: Vec::new(),
+            index: DetHashMap::default(),
+            max_lhs_len: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Stop completion with an error if the number of rules exceeds the limit.
+    pub fn with_max_rules(mut self, max_rules: usize) -> Self {
+        self.max_rules = Some(max_rules);
+        self
+    }
+
+    /// Complete the presentation to a confluent rewriting system.
+    /// An error is returned if the limit on the number of rules is exceeded.
+    pub fn complete(mut self) -> Result<RewritingSystem<A>, KnuthBendixError<A>> {
+        self.pending = std::mem::take(&mut self.relations);
+        self.process_pending()?;
+        // Check the critical pairs of each rule with the rules before it, including rules added along the way.
+        // Rules that are removed are added again as new rules, so every pair of the final rules is checked.
+        let mut next = 0;
+        while next < self.rules.len() {
+            for j in 0..=next {
+                let pairs = match (&self.rules[next], &self.rules[j]) {
+                    (Some(x), Some(y)) => {
+                        let mut pairs = critical_pairs(x, y);
+                        if j != next {
+                            pairs.extend(critical_pairs(y, x));
+                        }
+                        pairs
+                    }
+                    _ => continue,
+                };
+                self.pending.extend(pairs);
+                self.process_pending()?;
+            }
+            next += 1;
+        }
+        let mut rules: Vec<Rule> = self.rules.into_iter().flatten().collect();
+        rules.sort_by(|(x, _), (y, _)| shortlex(x).cmp(&shortlex(y)));
+        Ok(RewritingSystem::new(
+            self.alphabet,
+            self.contains_empty_word,
+            rules,
+        ))
+    }
+
+    /// Add rules for the pending equations, keeping the left hand sides of the rules reduced.
+    fn process_pending(&mut self) -> Result<(), KnuthBendixError<A>> {
+        while let Some((u, v)) = self.pending.pop() {
+            let u = self.reduce(&u);
+            let v = self.reduce(&v);
+            if u == v {
+                continue;
+            }
+            let (lhs, rhs) = if shortlex(&u) > shortlex(&v) {
+                (u, v)
+            } else {
+                (v, u)
+            };
+            // Rules whose left hand side can now be reduced are removed, and become equations to process again.
+            for slot in self.rules.iter_mut() {
+                if slot.as_ref().is_some_and(|(l, _)| contains(l, &lhs)) {
+                    let (l, r) = slot.take().unwrap();
+                    self.index.remove(&l);
+                    self.pending.push((l, r));
+                }
+            }
+            self.max_lhs_len = self.max_lhs_len.max(lhs.len());
+            self.index.insert(lhs.clone(), self.rules.len());
+            self.rules.push(Some((lhs.clone(), rhs)));
+            // Only the right hand sides containing the new left hand side can be reduced further.
+            for i in 0..self.rules.len() {
+                if let Some((_, rhs)) = self.rules[i].as_ref().filter(|(_, r)| contains(r, &lhs)) {
+                    let reduced = self.reduce(rhs);
+                    self.rules[i].as_mut().unwrap().1 = reduced;
+                }
+            }
+            if let Some(max_rules) = self.max_rules {
+                if self.index.len() > max_rules {
+                    return Err(KnuthBendixError::TooManyRules { max_rules });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn reduce(&self, word: &[usize]) -> Vec<usize> {
+        reduce(word, self.max_lhs_len, |lhs| {
+            self.index
+                .get(lhs)
+                .map(|&i| &self.rules[i].as_ref().unwrap().1[..])
+        })
+    }
+}
+
+/// A confluent rewriting system, where each word reduces to a unique normal form that is least in short-lex order.
+#[derive(Debug, Clone)]
+pub struct RewritingSystem<A>
+where
+    A: Ord + Clone,
+{
+    // The alphabet in increasing order
+    alphabet: Vec<A>,
+    contains_empty_word: bool,
+    // The rules in short-lex order of their left hand sides
+    rules: Vec<Rule>,
+    // Map from the left hand side of each rule to its position
+    index: DetHashMap<Vec<usize>, usize>,
+    max_lhs_len: usize,
+}
+
+impl<A> RewritingSystem<A>
+where
+    A: Ord + Clone + Hash + Display,
+{
+    fn new(alphabet: Vec<A>, contains_empty_word: bool, rules: Vec<Rule>) -> Self {
+        let index = rules
+            .iter()
+            .enumerate()
+            .map(|(i, (lhs, _))| (lhs.clone(), i))
+            .collect();
+        let max_lhs_len = rules.iter().map(|(lhs, _)| lhs.len()).max().unwrap_or(0);
+        Self {
+            alphabet,
+            contains_empty_word,
+            rules,
+            index,
+            max_lhs_len,
+        }
+    }
+
+    /// Return the symbols of the alphabet in increasing order.
+    pub fn alphabet(&self) -> &[A] {
+        &self.alphabet[..]
+    }
+
+    /// Return if the empty word represents the identity.
+    pub fn contains_empty_word(&self) -> bool {
+        self.contains_empty_word
+    }
+
+    /// Return the rules, where each left hand side is rewritten to its right hand side.
+    pub fn rules(&self) -> Vec<(Word<A>, Word<A>)> {
+        self.rules
+            .iter()
+            .map(|(lhs, rhs)| (self.to_word(lhs), self.to_word(rhs)))
+            .collect()
+    }
+
+    /// Return the number of rules.
+    pub fn number_of_rules(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Return the presentation with the rules as relations.
+    pub fn presentation(&self) -> Presentation<A> {
+        Presentation::from_vec_unchecked(
+            self.alphabet.clone(),
+            self.rules(),
+            self.contains_empty_word,
+        )
+    }
+
+    /// Reduce a word to its normal form, or return an error if it contains a symbol not in the alphabet.
+    pub fn reduce(&self, word: &Word<A>) -> Result<Word<A>, KnuthBendixError<A>> {
+        let word = to_indices(&self.alphabet, word)?;
+        let reduced = reduce(&word, self.max_lhs_len, |lhs| {
+            self.index.get(lhs).map(|&i| &self.rules[i].1[..])
+        });
+        Ok(self.to_word(&reduced))
+    }
+
+    /// Return if the words represent the same element, or an error if either contains a symbol not in the alphabet.
+    pub fn equal(&self, u: &Word<A>, v: &Word<A>) -> Result<bool, KnuthBendixError<A>> {
+        Ok(self.reduce(u)? == self.reduce(v)?)
+    }
+
+    /// Return if the semigroup is finite, i.e. there are finitely many normal forms.
+    pub fn is_finite(&self) -> bool {
+        self.count_normal_forms().is_some()
+    }
+
+    /// Return the number of elements, or None if the semigroup is infinite.
+    pub fn size(&self) -> Option<usize> {
+        let count = self.count_normal_forms()?;
+        // The count includes the empty word.
+        Some(if self.contains_empty_word {
+            count
+        } else {
+            count - 1
+        })
+    }
+
+    /// Return the normal form of each element in short-lex order, or None if the semigroup is infinite.
+    pub fn normal_forms(&self) -> Option<Vec<Word<A>>> {
+        self.count_normal_forms()?;
+        let automaton = self.automaton();
+        let mut normal_forms = Vec::new();
+        let mut layer = vec![(Vec::new(), 0)];
+        if self.contains_empty_word {
+            normal_forms.push(Word::empty_word());
+        }
+        // Extending each layer in order by each symbol in order keeps the words in short-lex order.
+        while !layer.is_empty() {
+            let mut next_layer = Vec::new();
+            for (word, state) in layer {
+                for (a, &target) in automaton.get_row(state).iter().enumerate() {
+                    if let Some(target) = target {
+                        let mut next: Vec<usize> = word.clone();
+                        next.push(a);
+                        normal_forms.push(self.to_word(&next));
+                        next_layer.push((next, target));
+                    }
+                }
+            }
+            layer = next_layer;
+        }
+        Some(normal_forms)
+    }
+
+    /// Count the irreducible words, including the empty word, or None if there are infinitely many.
+    /// These are the paths from the start of the automaton, which are finite if there are no cycles.
+    fn count_normal_forms(&self) -> Option<usize> {
+        let automaton = self.automaton();
+        let n_states = automaton.n_rows();
+        // None if unvisited, Some(None) if on the stack, and Some(Some(count)) once the count is known.
+        let mut counts: Vec<Option<Option<usize>>> = vec![None; n_states];
+        let mut stack = vec![(0, 0)];
+        counts[0] = Some(None);
+        while let Some((state, a)) = stack.last_mut() {
+            let state = *state;
+            match automaton.get_row(state).get(*a) {
+                Some(&target) => {
+                    *a += 1;
+                    if let Some(target) = target {
+                        match counts[target] {
+                            None => {
+                                counts[target] = Some(None);
+                                stack.push((target, 0));
+                            }
+                            // The target is on the stack, so there is a cycle.
+                            Some(None) => return None,
+                            Some(Some(_)) => {}
+                        }
+                    }
+                }
+                None => {
+                    stack.pop();
+                    let count = 1 + automaton
+                        .get_row(state)
+                        .iter()
+                        .flatten()
+                        .map(|&target| counts[target].unwrap().unwrap())
+                        .sum::<usize>();
+                    counts[state] = Some(Some(count));
+                }
+            }
+        }
+        counts[0].unwrap()
+    }
+
+    /// Build an automaton that accepts the irreducible words, from the Aho-Corasick automaton of the left hand sides.
+    /// Row i gives the transitions from state i by each symbol, which are None if the word becomes reducible.
+    /// State zero is the start state.
+    fn automaton(&self) -> Vec2<Option<usize>> {
+        let n = self.alphabet.len();
+        // Build the trie of the left hand sides.
+        let mut trie: Vec2<Option<usize>> = Vec2::new(1, n);
+        let mut terminal = vec![false];
+        for (lhs, _) in self.rules.iter() {
+            let mut state = 0;
+            for &a in lhs.iter() {
+                state = match trie[(state, a)] {
+                    Some(next) => next,
+                    None => {
+                        trie.add_row();
+                        terminal.push(false);
+                        let next = trie.n_rows() - 1;
+                        trie[(state, a)] = Some(next);
+                        next
+                    }
+                };
+            }
+            terminal[state] = true;
+        }
+        // Fill in the missing transitions by following the failure links, in breadth first order.
+        let mut goto: Vec2<usize> = Vec2::new(trie.n_rows(), n);
+        let mut fail = vec![0; trie.n_rows()];
+        let mut queue = VecDeque::from(vec![0]);
+        while let Some(state) = queue.pop_front() {
+            for a in 0..n {
+                match trie[(state, a)] {
+                    Some(child) => {
+                        if state != 0 {
+                            fail[child] = goto[(fail[state], a)];
+                            terminal[child] |= terminal[fail[child]];
+                        }
+                        goto[(state, a)] = child;
+                        queue.push_back(child);
+                    }
+                    None => goto[(state, a)] = goto[(fail[state], a)],
+                }
+            }
+        }
+        let mut automaton = Vec2::new(trie.n_rows(), n);
+        for state in 0..trie.n_rows() {
+            for a in 0..n {
+                let target = goto[(state, a)];
+                if !terminal[target] {
+                    automaton[(state, a)] = Some(target);
+                }
+            }
+        }
+        automaton
+    }
+
+    fn to_word(&self, word: &[usize]) -> Word<A> {
+        word.iter().map(|&a| self.alphabet[a].clone()).collect()
+    }
+}
+
+/// Convert a word to the positions of its symbols in the sorted alphabet.
+fn to_indices<A>(alphabet: &[A], word: &Word<A>) -> Result<Vec<usize>, KnuthBendixError<A>>
+where
+    A: Ord + Clone + Display,
+{
+    word.as_vec()
+        .iter()
+        .map(|a| {
+            alphabet
+                .binary_search(a)
+                .map_err(|_| KnuthBendixError::MissingSymbol { symbol: a.clone() })
+        })
+        .collect()
+}
+
+/// The key to compare words in short-lex order.
+fn shortlex(word: &[usize]) -> (usize, &[usize]) {
+    (word.len(), word)
+}
+
+/// Return if the word contains the subword.
+fn contains(word: &[usize], subword: &[usize]) -> bool {
+    word.windows(subword.len()).any(|window| window == subword)
+}
+
+/// Reduce a word with the rules, given the right hand side of the rule with each left hand side.
+/// Symbols are moved onto the output one at a time, so any left hand side in the output must be a suffix.
+fn reduce<'a, F>(word: &[usize], max_lhs_len: usize, rhs: F) -> Vec<usize>
+where
+    F: Fn(&[usize]) -> Option<&'a [usize]>,
+{
+    let mut output = Vec::with_capacity(word.len());
+    let mut input: Vec<usize> = word.iter().rev().copied().collect();
+    while let Some(a) = input.pop() {
+        output.push(a);
+        let rule = (1..=max_lhs_len.min(output.len()))
+            .find_map(|len| Some((len, rhs(&output[output.len() - len..])?)));
+        if let Some((len, rhs)) = rule {
+            output.truncate(output.len() - len);
+            input.extend(rhs.iter().rev());
+        }
+    }
+    output
+}
+
+/// Return the critical pairs from a suffix of the left hand side of x overlapping a prefix of the left hand side of y.
+/// The left hand sides must not contain each other.
+fn critical_pairs((x_lhs, x_rhs): &Rule, (y_lhs, y_rhs): &Rule) -> Vec<Rule> {
+    (1..x_lhs.len().min(y_lhs.len()))
+        .filter(|&k| x_lhs[x_lhs.len() - k..] == y_lhs[..k])
+        .map(|k| {
+            // The overlap is x_lhs y_lhs[k..] = x_lhs[..len - k] y_lhs.
+            let u = x_rhs.iter().chain(&y_lhs[k..]).copied().collect();
+            let v = x_lhs[..x_lhs.len() - k]
+                .iter()
+                .chain(y_rhs)
+                .copied()
+                .collect();
+            (u, v)
+        })
+        .collect()
+}
+
+#[derive(Debug, Snafu)]
+pub enum KnuthBendixError<A>
+where
+    A: Display,
+{
+    #[snafu(display("Symbol not in alphabet: {}", symbol))]
+    MissingSymbol { symbol: A },
+    #[snafu(display("Completion needed more than {} rules", max_rules))]
+    TooManyRules { max_rules: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::KnuthBendix;
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+            impls::transformation::TransformationSemigroup,
+            presentation::Presentation,
+            word::Word,
+        },
+    };
+
+    fn word(s: &str) -> Word<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn agrees_with_froidure_pin() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let rws = KnuthBendix::new(&res.presentation()).complete().unwrap();
+        assert_eq!(rws.size(), Some(27));
+        // The normal forms are the least words for each element, as Froidure-Pin finds.
+        let normal_forms = rws.normal_forms().unwrap();
+        assert_eq!(normal_forms, res.normal_forms());
+        for u in normal_forms.iter() {
+            for v in normal_forms.iter() {
+                let uv: Word<usize> = u.as_vec().iter().chain(v.as_vec()).copied().collect();
+                let product = res.word_to_position(&uv).unwrap();
+                assert_eq!(rws.reduce(&uv).unwrap(), res.normal_form(product).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn semigroup_presentation() {
+        // The cyclic semigroup with index 2 and period 3.
+        let p = Presentation::new(vec!['a'], vec![(word("aaaaa"), word("aa"))], false).unwrap();
+        let rws = KnuthBendix::new(&p).complete().unwrap();
+        assert_eq!(rws.size(), Some(4));
+        assert_eq!(
+            rws.normal_forms().unwrap(),
+            vec![word("a"), word("aa"), word("aaa"), word("aaaa")]
+        );
+        assert_eq!(rws.reduce(&word("aaaaaaaa")).unwrap(), word("aa"));
+        assert!(rws.reduce(&word("b")).is_err());
+    }
+
+    #[test]
+    fn infinite() {
+        // The free commutative monoid on two generators.
+        let p = Presentation::new(vec!['b', 'a'], vec![(word("ba"), word("ab"))], true).unwrap();
+        let rws = KnuthBendix::new(&p).complete().unwrap();
+        assert_eq!(rws.alphabet(), &['a', 'b']);
+        assert_eq!(rws.rules(), vec![(word("ba"), word("ab"))]);
+        assert!(!rws.is_finite());
+        assert_eq!(rws.size(), None);
+        assert_eq!(rws.normal_forms(), None);
+        assert!(rws.equal(&word("baba"), &word("aabb")).unwrap());
+        assert!(!rws.equal(&word("bab"), &word("aabb")).unwrap());
+    }
+
+    #[test]
+    fn too_many_rules() {
+        // The positive braid monoid on three strands has no finite complete system for this order.
+        let p = Presentation::new(vec!['a', 'b'], vec![(word("aba"), word("bab"))], true).unwrap();
+        assert!(KnuthBendix::new(&p).with_max_rules(20).complete().is_err());
+    }
+}
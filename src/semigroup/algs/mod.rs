@@ -1 +1,3 @@
 pub mod froidure_pin;
+pub mod knuth_bendix;
+pub mod todd_coxeter;
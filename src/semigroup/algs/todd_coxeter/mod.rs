@@ -0,0 +1,753 @@
+use std::fmt::Display;
+
+use snafu::Snafu;
+
+use crate::{
+    semigroup::{
+        algs::froidure_pin::{cayley_lookup, prefix_normal_form, EnumeratedSemigroup},
+        presentation::Presentation,
+        word::Word,
+    },
+    utils::{union_find::UnionFind, vec2::Vec2},
+    DetHashMap,
+};
+
+/// How new cosets are defined during the enumeration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strategy {
+    /// Haselgrove, Leech and Trotter: trace every relation from each coset in turn, defining cosets as needed.
+    Hlt,
+    /// Felsch: define cosets one at a time, and deduce all the consequences of the relations before the next definition.
+    /// This usually defines fewer cosets than HLT, but does more work for each one.
+    Felsch,
+}
+
+/// Todd-Coxeter coset enumeration for a finitely presented monoid or semigroup.
+///
+/// Generator i is the ith symbol of the alphabet of the presentation.
+/// The enumeration only terminates if the semigroup is finite, so a limit on the number of cosets can be set.
+/// ```
+/// use yatyat::semigroup::{
+///     algs::{
+///         froidure_pin::EnumeratedSemigroup,
+///         todd_coxeter::{Strategy, ToddCoxeter},
+///     },
+///     presentation::Presentation,
+///     word::Word,
+/// };
+///
+/// let word = |w: &[usize]| w.iter().copied().collect::<Word<usize>>();
+/// // The symmetric group of degree 3.
+/// let p = Presentation::new(
+///     vec![0, 1],
+///     vec![
+///         (word(&[0, 0]), word(&[])),
+///         (word(&[1, 1, 1]), word(&[])),
+///         (word(&[0, 1, 0, 1]), word(&[])),
+///     ],
+///     true,
+/// )
+/// .unwrap();
+/// for strategy in [Strategy::Hlt, Strategy::Felsch] {
+///     let res = ToddCoxeter::new(&p).with_strategy(strategy).build().unwrap();
+///     assert_eq!(res.size(), 6);
+///     assert_eq!(res.word_to_position(&word(&[1, 0])), res.word_to_position(&word(&[0, 1, 1])));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ToddCoxeter {
+    n_gens: usize,
+    relations: Vec<(Vec<usize>, Vec<usize>)>,
+    contains_empty_word: bool,
+    strategy: Strategy,
+    max_cosets: Option<usize>,
+    // The coset table, where entries may point to cosets that have since been identified with a smaller coset.
+    table: Vec2<Option<usize>>,
+    // Union-find of the cosets, where a coset is active if it is the representative of its set.
+    cosets: UnionFind,
+    n_active: usize,
+    // The (generator, coset) pairs that have been mapped to each coset, which may be out of date.
+    preimages: Vec<Vec<(usize, usize)>>,
+    coincidences: Vec<(usize, usize)>,
+    // Edges of the table that have been defined since their consequences were last deduced.
+    deductions: Vec<(usize, usize)>,
+}
+
+impl ToddCoxeter {
+    /// Create the enumeration of the given presentation, which uses the HLT strategy by default.
+    pub fn new<A>(presentation: &Presentation<A>) -> Self
+    where
+        A: Ord + Clone + std::hash::Hash + Display,
+    {
+        let positions: DetHashMap<&A, usize> = presentation
+            .alphabet()
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (a, i))
+            .collect();
+        // The presentation has already checked all of the symbols are in the alphabet.
+        let letters =
+            |word: &Word<A>| -> Vec<usize> { word.as_vec().iter().map(|a| positions[a]).collect() };
+        let relations = presentation
+            .relations()
+            .iter()
+            .map(|(lhs, rhs)| (letters(lhs), letters(rhs)))
+            .collect();
+        let n_gens = presentation.alphabet().len();
+        Self {
+            n_gens,
+            relations,
+            contains_empty_word: presentation.contains_empty_word(),
+            strategy: Strategy::Hlt,
+            max_cosets: None,
+            table: Vec2::new(0, n_gens),
+            cosets: UnionFind::new(0),
+            n_active: 0,
+            preimages: Vec::new(),
+            coincidences: Vec::new(),
+            deductions: Vec::new(),
+        }
+    }
+
+    /// Set the strategy used to define new cosets.
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Stop the enumeration with an error if the number of active cosets exceeds the limit.
+    pub fn with_max_cosets(mut self, max_cosets: usize) -> Self {
+        self.max_cosets = Some(max_cosets);
+        self
+    }
+
+    /// Return the strategy used to define new cosets.
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Enumerate the cosets, returning the elements in short-lex order of their normal forms.
+    /// An error is returned if the limit on the number of cosets is exceeded.
+    pub fn build(mut self) -> Result<ToddCoxeterResult, ToddCoxeterError> {
+        // Coset zero is the empty word, which is removed at the end for a semigroup.
+        self.new_coset()?;
+        match self.strategy {
+            Strategy::Hlt => self.hlt()?,
+            Strategy::Felsch => self.felsch()?,
+        }
+        self.check_relations();
+        Ok(self.standardize())
+    }
+
+    fn hlt(&mut self) -> Result<(), ToddCoxeterError> {
+        let mut coset = 0;
+        while coset < self.cosets.n_points() {
+            for r in 0..self.relations.len() {
+                if !self.is_active(coset) {
+                    break;
+                }
+                let x = self.trace_and_define(coset, r, false)?;
+                let y = self.trace_and_define(coset, r, true)?;
+                self.identify(x, y);
+            }
+            self.define_row(coset)?;
+            coset += 1;
+        }
+        Ok(())
+    }
+
+    fn felsch(&mut self) -> Result<(), ToddCoxeterError> {
+        let mut coset = 0;
+        while coset < self.cosets.n_points() {
+            for a in 0..self.n_gens {
+                if !self.is_active(coset) {
+                    break;
+                }
+                if self.table[(coset, a)].is_none() {
+                    self.define(coset, a)?;
+                    self.process_deductions();
+                }
+            }
+            coset += 1;
+        }
+        Ok(())
+    }
+
+    /// Add a new coset, or return an error if there would be too many.
+    fn new_coset(&mut self) -> Result<usize, ToddCoxeterError> {
+        if let Some(max_cosets) = self.max_cosets {
+            if self.n_active >= max_cosets {
+                return Err(ToddCoxeterError::TooManyCosets { max_cosets });
+            }
+        }
+        let coset = self.cosets.add();
+        self.table.add_row();
+        self.preimages.push(Vec::new());
+        self.n_active += 1;
+        Ok(coset)
+    }
+
+    /// Define the image of the coset under the generator as a new coset.
+    fn define(&mut self, coset: usize, a: usize) -> Result<usize, ToddCoxeterError> {
+        let target = self.new_coset()?;
+        self.set(coset, a, target);
+        Ok(target)
+    }
+
+    /// Define the image of the active coset under each generator where it is missing.
+    fn define_row(&mut self, coset: usize) -> Result<(), ToddCoxeterError> {
+        for a in 0..self.n_gens {
+            if self.is_active(coset) && self.table[(coset, a)].is_none() {
+                self.define(coset, a)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set(&mut self, coset: usize, a: usize, target: usize) {
+        self.table[(coset, a)] = Some(target);
+        self.preimages[target].push((a, coset));
+        if self.strategy == Strategy::Felsch {
+            self.deductions.push((coset, a));
+        }
+    }
+
+    fn is_active(&self, coset: usize) -> bool {
+        self.cosets.is_root(coset)
+    }
+
+    /// Return the image of the coset under the generator, if it is defined.
+    fn image(&mut self, coset: usize, a: usize) -> Option<usize> {
+        let coset = self.cosets.find(coset);
+        let target = self.table[(coset, a)]?;
+        Some(self.cosets.find(target))
+    }
+
+    /// Follow a side of a relation from the coset, defining new cosets where the image is missing.
+    fn trace_and_define(
+        &mut self,
+        coset: usize,
+        relation: usize,
+        rhs: bool,
+    ) -> Result<usize, ToddCoxeterError> {
+        let mut current = coset;
+        for i in 0..self.side(relation, rhs).len() {
+            let a = self.side(relation, rhs)[i];
+            current = match self.image(current, a) {
+                Some(next) => next,
+                None => self.define(current, a)?,
+            };
+        }
+        Ok(current)
+    }
+
+    fn side(&self, relation: usize, rhs: bool) -> &[usize] {
+        let (lhs_word, rhs_word) = &self.relations[relation];
+        if rhs {
+            rhs_word
+        } else {
+            lhs_word
+        }
+    }
+
+    /// Identify two cosets, along with all of the cosets this forces to be equal.
+    fn identify(&mut self, x: usize, y: usize) {
+        self.coincidences.push((x, y));
+        while let Some((x, y)) = self.coincidences.pop() {
+            let (x, y) = (self.cosets.find(x), self.cosets.find(y));
+            if x == y {
+                continue;
+            }
+            // Keep the smaller coset, so coset zero is always active.
+            let (keep, remove) = (x.min(y), x.max(y));
+            self.cosets.union_keep_smaller(keep, remove);
+            self.n_active -= 1;
+            let preimages = std::mem::take(&mut self.preimages[remove]);
+            // Edges into the removed coset now lead to the kept coset, so their consequences may have changed.
+            if self.strategy == Strategy::Felsch {
+                self.deductions
+                    .extend(preimages.iter().map(|&(a, c)| (c, a)));
+            }
+            self.preimages[keep].extend(preimages);
+            for a in 0..self.n_gens {
+                if let Some(target) = self.table[(remove, a)] {
+                    match self.table[(keep, a)] {
+                        Some(other) => self.coincidences.push((target, other)),
+                        None => {
+                            let target = self.cosets.find(target);
+                            self.set(keep, a, target);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Deduce the consequences of the new edges, by scanning the relations through each of them without defining cosets.
+    fn process_deductions(&mut self) {
+        while let Some((coset, a)) = self.deductions.pop() {
+            let coset = self.cosets.find(coset);
+            for r in 0..self.relations.len() {
+                for rhs in [false, true] {
+                    let positions: Vec<usize> = self
+                        .side(r, rhs)
+                        .iter()
+                        .enumerate()
+                        .filter(|&(_, &b)| b == a)
+                        .map(|(i, _)| i)
+                        .collect();
+                    for i in positions {
+                        let prefix = self.side(r, rhs)[..i].to_vec();
+                        for start in self.sources(coset, &prefix) {
+                            self.scan(start, r);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Return the active cosets that reach the coset by following the word.
+    fn sources(&mut self, coset: usize, word: &[usize]) -> Vec<usize> {
+        let mut current = vec![self.cosets.find(coset)];
+        for &b in word.iter().rev() {
+            let mut previous = Vec::new();
+            for &c in current.iter() {
+                for j in 0..self.preimages[c].len() {
+                    let (label, source) = self.preimages[c][j];
+                    if label == b && self.is_active(source) && self.image(source, b) == Some(c) {
+                        previous.push(source);
+                    }
+                }
+            }
+            previous.sort_unstable();
+            previous.dedup();
+            current = previous;
+        }
+        current
+    }
+
+    /// Scan the relation from the coset without defining any cosets.
+    /// If both sides can be followed their ends are identified, and if one side is only missing its last edge it is filled in.
+    fn scan(&mut self, coset: usize, relation: usize) {
+        let mut ends = [None, None];
+        let mut stuck = [None, None];
+        for (side, &rhs) in [false, true].iter().enumerate() {
+            let word = self.side(relation, rhs).to_vec();
+            let mut current = self.cosets.find(coset);
+            let mut complete = true;
+            for (i, &a) in word.iter().enumerate() {
+                match self.image(current, a) {
+                    Some(next) => current = next,
+                    None => {
+                        if i + 1 == word.len() {
+                            stuck[side] = Some((current, a));
+                        }
+                        complete = false;
+                        break;
+                    }
+                }
+            }
+            if complete {
+                ends[side] = Some(current);
+            }
+        }
+        match (ends, stuck) {
+            ([Some(x), Some(y)], _) => self.identify(x, y),
+            ([Some(x), None], [_, Some((c, a))]) | ([None, Some(x)], [Some((c, a)), _]) => {
+                self.set(c, a, x)
+            }
+            _ => {}
+        }
+    }
+
+    /// Check every relation holds at every active coset in the complete table, identifying the ends where they do not.
+    fn check_relations(&mut self) {
+        loop {
+            let mut changed = false;
+            for coset in 0..self.cosets.n_points() {
+                for r in 0..self.relations.len() {
+                    if !self.is_active(coset) {
+                        break;
+                    }
+                    let mut ends = [coset, coset];
+                    for (side, &rhs) in [false, true].iter().enumerate() {
+                        for i in 0..self.side(r, rhs).len() {
+                            let a = self.side(r, rhs)[i];
+                            ends[side] = self.image(ends[side], a).unwrap();
+                        }
+                    }
+                    if ends[0] != ends[1] {
+                        self.identify(ends[0], ends[1]);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+    }
+
+    /// Renumber the active cosets in short-lex order of their least words, by a breadth first search from coset zero.
+    fn standardize(mut self) -> ToddCoxeterResult {
+        let mut position = vec![None; self.cosets.n_points()];
+        let mut order = vec![0];
+        let mut prefix = vec![None];
+        position[0] = Some(0);
+        let mut next = 0;
+        while next < order.len() {
+            let coset = order[next];
+            for a in 0..self.n_gens {
+                let target = self.image(coset, a).unwrap();
+                if position[target].is_none() {
+                    position[target] = Some(order.len());
+                    order.push(target);
+                    prefix.push(Some((next, a)));
+                }
+            }
+            next += 1;
+        }
+        let size = order.len();
+        let mut right = Vec2::new(size, self.n_gens);
+        for (i, &coset) in order.iter().enumerate() {
+            for a in 0..self.n_gens {
+                let target = self.image(coset, a).unwrap();
+                right[(i, a)] = position[target];
+            }
+        }
+        // If x = ya then bx = (by)a, and by comes before x.
+        let mut left: Vec2<Option<usize>> = Vec2::new(size, self.n_gens);
+        for x in 0..size {
+            for b in 0..self.n_gens {
+                left[(x, b)] = match prefix[x] {
+                    None => right[(0, b)],
+                    Some((y, a)) => right[(left[(y, b)].unwrap(), a)],
+                };
+            }
+        }
+        // Element x is the element at prefix[x] multiplied by a generator, where None is the empty word.
+        let prefix: Vec<Option<(Option<usize>, usize)>> = prefix
+            .into_iter()
+            .map(|p| p.map(|(y, a)| (Some(y), a)))
+            .collect();
+        let generators: Vec<usize> = right.get_row(0).iter().map(|x| x.unwrap()).collect();
+        if self.contains_empty_word {
+            ToddCoxeterResult {
+                is_monoid: true,
+                right_cayley_graph: right,
+                left_cayley_graph: left,
+                prefix,
+                generators,
+            }
+        } else {
+            // Remove the empty word, which no other word is equal to in a semigroup.
+            let shift = |graph: &Vec2<Option<usize>>| {
+                let mut shifted = Vec2::new(size - 1, self.n_gens);
+                for x in 1..size {
+                    for a in 0..self.n_gens {
+                        shifted[(x - 1, a)] = graph[(x, a)].map(|y| y - 1);
+                    }
+                }
+                shifted
+            };
+            ToddCoxeterResult {
+                is_monoid: false,
+                right_cayley_graph: shift(&right),
+                left_cayley_graph: shift(&left),
+                prefix: prefix[1..]
+                    .iter()
+                    .map(|p| p.map(|(y, a)| (y.filter(|&y| y != 0).map(|y| y - 1), a)))
+                    .collect(),
+                generators: generators.into_iter().map(|x| x - 1).collect(),
+            }
+        }
+    }
+}
+
+/// The result of Todd-Coxeter, which has the same layout as `FroidurePinResult`,
+/// so the algorithms on an `EnumeratedSemigroup` can be run on it.
+///
+/// Elements are given by their position in short-lex order of their normal forms.
+/// If the presentation is for a monoid, then the identity is at position zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ToddCoxeterResult {
+    is_monoid: bool,
+    // The left and right Cayley graphs, which index into the elements.
+    right_cayley_graph: Vec2<Option<usize>>,
+    left_cayley_graph: Vec2<Option<usize>>,
+    // The normal form of each element is the normal form of the element at the first position followed by the generator,
+    // where None is the empty word. The identity of a monoid has no prefix.
+    prefix: Vec<Option<(Option<usize>, usize)>>,
+    // The position of each generator
+    generators: Vec<usize>,
+}
+
+impl EnumeratedSemigroup for ToddCoxeterResult {
+    fn size(&self) -> usize {
+        self.right_cayley_graph.n_rows()
+    }
+
+    fn is_monoid(&self) -> bool {
+        self.is_monoid
+    }
+
+    fn number_of_generators(&self) -> usize {
+        self.right_cayley_graph.n_cols()
+    }
+
+    fn generator_position(&self, generator: usize) -> Option<usize> {
+        self.generators.get(generator).copied()
+    }
+
+    fn right_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        cayley_lookup(&self.right_cayley_graph, element, generator)
+    }
+
+    fn left_cayley(&self, element: usize, generator: usize) -> Option<usize> {
+        cayley_lookup(&self.left_cayley_graph, element, generator)
+    }
+
+    fn normal_form(&self, index: usize) -> Option<Word<usize>> {
+        prefix_normal_form(&self.prefix, index)
+    }
+}
+
+/// Remove relations that follow from the other relations of the presentation.
+///
+/// The relations are considered from last to first, and a relation is removed if its sides are equal in the
+/// semigroup presented by the remaining relations, which is checked by enumerating it with [`ToddCoxeter`].
+/// The relation is kept if the enumeration needs more than `max_cosets` cosets, so every relation removed is a
+/// consequence of those kept and the presentation still defines the same semigroup.
+/// True is returned if every enumeration finished, in which case none of the relations kept follows from the
+/// others. False is returned if some relations were kept because an enumeration needed too many cosets,
+/// which is always the case when removing a relation leaves an infinite semigroup.
+/// ```
+/// use yatyat::semigroup::{
+///     algs::todd_coxeter::remove_implied_relations, presentation::Presentation, word::Word,
+/// };
+///
+/// let word = |s: &str| -> Word<char> { s.chars().collect() };
+/// // The symmetric group of degree 3, where bab = a follows from the other relations.
+/// let mut p = Presentation::new(
+///     vec!['a', 'b'],
+///     vec![
+///         (word("aa"), word("")),
+///         (word("bbb"), word("")),
+///         (word("abab"), word("")),
+///         (word("bab"), word("a")),
+///     ],
+///     true,
+/// )
+/// .unwrap();
+/// // Without abab = 1 the presentation is infinite, so it is kept without finishing the enumeration.
+/// assert!(!remove_implied_relations(&mut p, 100));
+/// assert_eq!(p.relations().len(), 3);
+/// ```
+pub fn remove_implied_relations<A>(presentation: &mut Presentation<A>, max_cosets: usize) -> bool
+where
+    A: Ord + Clone + std::hash::Hash + Display,
+{
+    presentation.remove_trivial_relations();
+    let positions: DetHashMap<A, usize> = presentation
+        .alphabet()
+        .iter()
+        .enumerate()
+        .map(|(i, a)| (a.clone(), i))
+        .collect();
+    let letters =
+        |word: &Word<A>| -> Word<usize> { word.as_vec().iter().map(|a| positions[a]).collect() };
+    let mut finished = true;
+    for i in (0..presentation.relations().len()).rev() {
+        let (lhs, rhs) = presentation.relations_mut().remove(i);
+        let implied = match ToddCoxeter::new(presentation)
+            .with_max_cosets(max_cosets)
+            .build()
+        {
+            Ok(tc) => tc.word_to_position(&letters(&lhs)) == tc.word_to_position(&letters(&rhs)),
+            Err(_) => {
+                finished = false;
+                false
+            }
+        };
+        if !implied {
+            presentation.relations_mut().insert(i, (lhs, rhs));
+        }
+    }
+    finished
+}
+
+#[derive(Debug, Snafu)]
+pub enum ToddCoxeterError {
+    #[snafu(display("Enumeration needed more than {} cosets", max_cosets))]
+    TooManyCosets { max_cosets: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{remove_implied_relations, Strategy, ToddCoxeter};
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{
+                congruence::{Congruence, CongruenceKind},
+                froidure_pin_impl::FroidurePin,
+                ideal::{Ideal, IdealKind},
+                EnumeratedSemigroup, FroidurePinBuilder, Side,
+            },
+            impls::transformation::TransformationSemigroup,
+            presentation::Presentation,
+            word::Word,
+        },
+    };
+
+    const STRATEGIES: [Strategy; 2] = [Strategy::Hlt, Strategy::Felsch];
+
+    fn word(w: &[usize]) -> Word<usize> {
+        w.iter().copied().collect()
+    }
+
+    #[test]
+    fn agrees_with_froidure_pin() {
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(4, vec![1, 0, 2, 3]).unwrap(),
+            Transformation::from_vec(4, vec![1, 2, 3, 0]).unwrap(),
+            Transformation::from_vec(4, vec![0, 0, 2, 3]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let p = res.presentation();
+        for strategy in STRATEGIES.iter().copied() {
+            let tc = ToddCoxeter::new(&p)
+                .with_strategy(strategy)
+                .build()
+                .unwrap();
+            assert!(tc.is_monoid());
+            assert_eq!(tc.size(), 256);
+            assert_eq!(tc.number_of_generators(), 3);
+            // Elements are in the same order, so the Cayley graphs are identical.
+            assert_eq!(tc.normal_forms(), res.normal_forms());
+            for x in 0..tc.size() {
+                for a in 0..3 {
+                    assert_eq!(tc.right_cayley(x, a), res.right_cayley(x, a));
+                    assert_eq!(tc.left_cayley(x, a), res.left_cayley(x, a));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn algorithms_on_result() {
+        // The full transformation monoid of degree 3.
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+            Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let tc = ToddCoxeter::new(&res.presentation()).build().unwrap();
+        // Elements are in the same order, so everything computed from the Cayley graphs is identical.
+        assert_eq!(tc.greens_relations(), res.greens_relations());
+        assert_eq!(tc.minimal_ideal(), res.minimal_ideal());
+        assert_eq!(
+            Ideal::new(&tc, IdealKind::Left, &[5]),
+            Ideal::new(&res, IdealKind::Left, &[5])
+        );
+        assert_eq!(
+            tc.congruence_lattice(CongruenceKind::TwoSided)
+                .congruences(),
+            res.congruence_lattice(CongruenceKind::TwoSided)
+                .congruences()
+        );
+        let congruence = Congruence::new(&tc, CongruenceKind::TwoSided, &[(0, 1)]).unwrap();
+        assert_eq!(
+            congruence.quotient(&tc),
+            Congruence::new(&res, CongruenceKind::TwoSided, &[(0, 1)])
+                .unwrap()
+                .quotient(&res)
+        );
+        assert_eq!(
+            tc.cayley_graph(Side::Right).to_dot(),
+            res.cayley_graph(Side::Right).to_dot()
+        );
+    }
+
+    #[test]
+    fn semigroup_presentation() {
+        // The rectangular band with two rows and two columns.
+        let p = Presentation::new(
+            vec![0, 1],
+            vec![
+                (word(&[0, 0]), word(&[0])),
+                (word(&[1, 1]), word(&[1])),
+                (word(&[0, 1, 0]), word(&[0])),
+                (word(&[1, 0, 1]), word(&[1])),
+            ],
+            false,
+        )
+        .unwrap();
+        for strategy in STRATEGIES.iter().copied() {
+            let tc = ToddCoxeter::new(&p)
+                .with_strategy(strategy)
+                .build()
+                .unwrap();
+            assert!(!tc.is_monoid());
+            assert_eq!(
+                tc.normal_forms(),
+                vec![word(&[0]), word(&[1]), word(&[0, 1]), word(&[1, 0])]
+            );
+            assert_eq!(tc.word_to_position(&word(&[0, 1, 1, 0, 1])), Some(2));
+            assert_eq!(tc.word_to_position(&word(&[])), None);
+            assert_eq!(tc.left_cayley(1, 0), Some(2));
+        }
+    }
+
+    #[test]
+    fn too_many_cosets() {
+        // The free commutative monoid on two generators is infinite.
+        let p = Presentation::new(vec![0, 1], vec![(word(&[1, 0]), word(&[0, 1]))], true).unwrap();
+        for strategy in STRATEGIES.iter().copied() {
+            let tc = ToddCoxeter::new(&p)
+                .with_strategy(strategy)
+                .with_max_cosets(100);
+            assert_eq!(tc.strategy(), strategy);
+            assert!(tc.build().is_err());
+        }
+    }
+
+    #[test]
+    fn froidure_pin_presentation() {
+        // Symmetric group on 3 points, generated by a transposition and a 3-cycle.
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let mut p = res.presentation();
+        // Some of the rules follow from the others, and the rest still present the same group.
+        // Removing any of the rest gives an infinite semigroup, so the enumerations do not all finish.
+        assert!(!remove_implied_relations(&mut p, 100));
+        assert!(p.relations().len() < res.rules().len());
+        assert!(p.relations().iter().all(|r| res.rules().contains(r)));
+        assert_eq!(p.to_string(), "<0, 1 | 0 0 = ε, 0 1 0 = 1 1, 1 0 1 = 0>");
+        assert_eq!(p.normal_forms(), Some(&res.normal_forms()[..]));
+        let tc = ToddCoxeter::new(&p).build().unwrap();
+        assert_eq!(tc.normal_forms(), res.normal_forms());
+    }
+
+    #[test]
+    fn implied_relations() {
+        let word = |n: usize| std::iter::repeat(0).take(n).collect::<Word<usize>>();
+        // The cyclic group of order 3, where a^6 = 1 follows from a^3 = 1 but not the other way around.
+        let relations = vec![(word(6), word(0)), (word(3), word(0))];
+        let mut p = Presentation::new(vec![0], relations.clone(), true).unwrap();
+        assert!(remove_implied_relations(&mut p, 100));
+        assert_eq!(p.relations(), &[(word(3), word(0))]);
+        // Too few cosets to enumerate the cyclic group of order 6, so it is not known if a^3 = 1 follows from a^6 = 1.
+        let mut p = Presentation::new(vec![0], relations, true).unwrap();
+        assert!(!remove_implied_relations(&mut p, 5));
+        assert_eq!(p.relations(), &[(word(3), word(0))]);
+    }
+}
@@ -0,0 +1,182 @@
+use std::fmt::Display;
+
+use crate::element::bipartition::{err::BipartitionError, Bipartition};
+
+use super::super::Semigroup;
+
+/// Struct that represents a semigroup of bipartitions, such as the partition, Brauer and Temperley-Lieb monoids.
+pub struct BipartitionSemigroup {
+    degree: usize,
+    generators: Vec<Bipartition>,
+}
+
+impl BipartitionSemigroup {
+    /// Create a new BipartitionSemigroup from a list of generators.
+    /// The new generators must have the same degree, otherwise an error is returned.
+    pub fn new(gens: &[Bipartition]) -> Result<Self, BipartitionError> {
+        // Take degree of first element as degree of the semigroup. Need to handle trivial case.
+        let degree = gens.first().map(|f| f.degree()).unwrap_or(0);
+        // Must have same degree for all values
+        if let Some(f) = gens.iter().skip(1).find(|f| f.degree() != degree) {
+            Err(BipartitionError::MismatchingDegree {
+                degree1: degree,
+                degree2: f.degree(),
+            })
+        } else {
+            Ok(BipartitionSemigroup {
+                degree,
+                generators: gens.to_vec(),
+            })
+        }
+    }
+
+    /// The partition monoid of the given degree, which contains all bipartitions.
+    /// ```
+    /// use yatyat::semigroup::{
+    ///     algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+    ///     impls::bipartition::BipartitionSemigroup,
+    /// };
+    ///
+    /// let s = BipartitionSemigroup::partition_monoid(2);
+    /// assert_eq!(FroidurePin::new(&s).build().size(), 15);
+    /// ```
+    pub fn partition_monoid(degree: usize) -> Self {
+        let mut generators = Self::symmetric_group_generators(degree);
+        if degree >= 1 {
+            // Separate 0 from 0'
+            let mut lookup: Vec<usize> = (0..degree).chain(0..degree).collect();
+            lookup[degree] = degree;
+            generators.push(Bipartition::from_vec_unchecked(degree, lookup));
+        }
+        if degree >= 2 {
+            // Merge the blocks of 0 and 1
+            let mut lookup: Vec<usize> = (0..degree).chain(0..degree).collect();
+            lookup[1] = 0;
+            lookup[degree + 1] = 0;
+            generators.push(Bipartition::from_vec_unchecked(degree, lookup));
+        }
+        Self { degree, generators }
+    }
+
+    /// The Brauer monoid of the given degree, which contains all bipartitions with blocks of size two.
+    pub fn brauer_monoid(degree: usize) -> Self {
+        let mut generators = Self::symmetric_group_generators(degree);
+        if degree >= 2 {
+            generators.push(Self::temperley_lieb_generator(degree, 0));
+        }
+        Self { degree, generators }
+    }
+
+    /// The Temperley-Lieb monoid of the given degree, which contains all planar bipartitions with blocks of size two.
+    pub fn temperley_lieb_monoid(degree: usize) -> Self {
+        let generators = (0..degree.saturating_sub(1))
+            .map(|i| Self::temperley_lieb_generator(degree, i))
+            .collect();
+        Self { degree, generators }
+    }
+
+    /// Generators for the symmetric group, given by a transposition and a cycle.
+    fn symmetric_group_generators(degree: usize) -> Vec<Bipartition> {
+        if degree < 2 {
+            return Vec::new();
+        }
+        let transposition = (0..degree).chain([1, 0].iter().copied().chain(2..degree));
+        let cycle = (0..degree).chain(std::iter::once(degree - 1).chain(0..degree - 1));
+        vec![
+            Bipartition::from_vec_unchecked(degree, transposition.collect()),
+            Bipartition::from_vec_unchecked(degree, cycle.collect()),
+        ]
+    }
+
+    /// The bipartition with blocks {i, i + 1}, {i', (i + 1)'}, and {j, j'} otherwise.
+    fn temperley_lieb_generator(degree: usize, i: usize) -> Bipartition {
+        let mut lookup: Vec<usize> = (0..degree).chain(0..degree).collect();
+        lookup[i + 1] = i;
+        lookup[degree + i] = degree;
+        lookup[degree + i + 1] = degree;
+        Bipartition::from_vec_unchecked(degree, lookup)
+    }
+
+    /// Return the degree of the bipartitions in this Semigroup
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+}
+
+impl Display for BipartitionSemigroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<")?;
+        let mut sep = "";
+        for gen in self.generators() {
+            write!(f, "{}{}", sep, gen)?;
+            sep = ", "
+        }
+        write!(f, ">")
+    }
+}
+
+impl Semigroup<Bipartition> for BipartitionSemigroup {
+    fn generators(&self) -> &[Bipartition] {
+        &self.generators[..]
+    }
+
+    fn id(&self) -> Option<Bipartition> {
+        Some(Bipartition::id(self.degree))
+    }
+
+    fn is_id(&self, element: &Bipartition) -> bool {
+        element.degree() == self.degree && element.is_id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        element::bipartition::Bipartition,
+        semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+    };
+
+    use super::BipartitionSemigroup;
+
+    #[test]
+    fn trivial_semigroup() {
+        assert!(BipartitionSemigroup::new(&[]).is_ok());
+    }
+
+    #[test]
+    fn invalid_gens() {
+        let s = BipartitionSemigroup::new(&[Bipartition::id(2), Bipartition::id(3)]);
+        assert!(s.is_err());
+    }
+
+    #[test]
+    fn partition_monoid() {
+        // The sizes are the Bell numbers B(2n)
+        for (n, size) in [(1, 2), (2, 15), (3, 203)].iter() {
+            let s = BipartitionSemigroup::partition_monoid(*n);
+            assert_eq!(FroidurePin::new(&s).build().size(), *size);
+        }
+    }
+
+    #[test]
+    fn brauer_monoid() {
+        // The sizes are (2n - 1)!!
+        for (n, size) in [(1, 1), (2, 3), (3, 15), (4, 105)].iter() {
+            let s = BipartitionSemigroup::brauer_monoid(*n);
+            assert_eq!(FroidurePin::new(&s).build().size(), *size);
+        }
+    }
+
+    #[test]
+    fn temperley_lieb_monoid() {
+        // The sizes are the Catalan numbers
+        for (n, size) in [(1, 1), (2, 2), (3, 5), (4, 14), (5, 42)].iter() {
+            let s = BipartitionSemigroup::temperley_lieb_monoid(*n);
+            assert_eq!(FroidurePin::new(&s).build().size(), *size);
+        }
+        assert_eq!(
+            BipartitionSemigroup::temperley_lieb_monoid(2).to_string(),
+            "<[{0, 1}, {0', 1'}]>"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt::Display;
+
+use crate::element::boolean_mat::{err::BooleanMatError, BooleanMat};
+
+use super::super::Semigroup;
+
+/// Struct that represents a semigroup of boolean matrices, such as the monoid of binary relations.
+pub struct BooleanMatSemigroup {
+    dimension: usize,
+    generators: Vec<BooleanMat>,
+}
+
+impl BooleanMatSemigroup {
+    /// Create a new BooleanMatSemigroup from a list of generators.
+    /// The new generators must have the same dimension, otherwise an error is returned.
+    pub fn new(gens: &[BooleanMat]) -> Result<Self, BooleanMatError> {
+        // Take dimension of first element as dimension of the semigroup. Need to handle trivial case.
+        let dimension = gens.first().map(|f| f.dimension()).unwrap_or(0);
+        // Must have same dimension for all values
+        if let Some(f) = gens.iter().skip(1).find(|f| f.dimension() != dimension) {
+            Err(BooleanMatError::MismatchingDimension {
+                dimension1: dimension,
+                dimension2: f.dimension(),
+            })
+        } else {
+            Ok(BooleanMatSemigroup {
+                dimension,
+                generators: gens.to_vec(),
+            })
+        }
+    }
+
+    /// Return the dimension of the matrices in this Semigroup
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+}
+
+impl Display for BooleanMatSemigroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<")?;
+        let mut sep = "";
+        for gen in self.generators() {
+            write!(f, "{}{}", sep, gen)?;
+            sep = ", "
+        }
+        write!(f, ">")
+    }
+}
+
+impl Semigroup<BooleanMat> for BooleanMatSemigroup {
+    fn generators(&self) -> &[BooleanMat] {
+        &self.generators[..]
+    }
+
+    fn id(&self) -> Option<BooleanMat> {
+        Some(BooleanMat::id(self.dimension))
+    }
+
+    fn is_id(&self, element: &BooleanMat) -> bool {
+        element.dimension() == self.dimension && element.is_id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        element::boolean_mat::BooleanMat,
+        semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+    };
+
+    use super::BooleanMatSemigroup;
+
+    #[test]
+    fn trivial_semigroup() {
+        assert!(BooleanMatSemigroup::new(&[]).is_ok());
+    }
+
+    #[test]
+    fn invalid_gens() {
+        let s = BooleanMatSemigroup::new(&[BooleanMat::id(2), BooleanMat::id(3)]);
+        assert!(s.is_err());
+    }
+
+    #[test]
+    fn binary_relation_monoid_2() {
+        let s = BooleanMatSemigroup::new(&[
+            BooleanMat::from_vec(2, vec![vec![false, true], vec![true, false]]).unwrap(),
+            BooleanMat::from_vec(2, vec![vec![true, false], vec![true, true]]).unwrap(),
+            BooleanMat::from_vec(2, vec![vec![true, false], vec![false, false]]).unwrap(),
+            BooleanMat::from_vec(2, vec![vec![true, true], vec![true, false]]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        // All binary relations on 2 points
+        assert_eq!(res.size(), 16);
+    }
+}
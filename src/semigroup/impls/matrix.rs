@@ -0,0 +1,155 @@
+use std::fmt::Display;
+
+use crate::element::matrix::{err::MatrixError, semiring::Semiring, Matrix};
+
+use super::super::Semigroup;
+
+/// Struct that represents a semigroup of matrices over a semiring.
+pub struct MatrixSemigroup<S>
+where
+    S: Semiring,
+{
+    semiring: S,
+    dimension: usize,
+    generators: Vec<Matrix<S>>,
+}
+
+impl<S> MatrixSemigroup<S>
+where
+    S: Semiring,
+{
+    /// Create a new MatrixSemigroup over the given semiring from a list of generators.
+    /// The new generators must have the same dimension and be over the given semiring, otherwise an error is returned.
+    pub fn new(semiring: S, gens: &[Matrix<S>]) -> Result<Self, MatrixError> {
+        // Take dimension of first element as dimension of the semigroup. Need to handle trivial case.
+        let dimension = gens.first().map(|f| f.dimension()).unwrap_or(0);
+        // Must have same dimension for all values
+        if let Some(f) = gens.iter().skip(1).find(|f| f.dimension() != dimension) {
+            Err(MatrixError::MismatchingDimension {
+                dimension1: dimension,
+                dimension2: f.dimension(),
+            })
+        } else if gens.iter().any(|f| *f.semiring() != semiring) {
+            Err(MatrixError::MismatchingSemiring {})
+        } else {
+            Ok(MatrixSemigroup {
+                semiring,
+                dimension,
+                generators: gens.to_vec(),
+            })
+        }
+    }
+
+    /// Return the dimension of the matrices in this Semigroup
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Return the semiring the matrices are defined over
+    pub fn semiring(&self) -> &S {
+        &self.semiring
+    }
+}
+
+impl<S> Display for MatrixSemigroup<S>
+where
+    S: Semiring,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<")?;
+        let mut sep = "";
+        for gen in self.generators() {
+            write!(f, "{}{}", sep, gen)?;
+            sep = ", "
+        }
+        write!(f, ">")
+    }
+}
+
+impl<S> Semigroup<Matrix<S>> for MatrixSemigroup<S>
+where
+    S: Semiring,
+{
+    fn generators(&self) -> &[Matrix<S>] {
+        &self.generators[..]
+    }
+
+    fn id(&self) -> Option<Matrix<S>> {
+        Some(Matrix::id(self.semiring.clone(), self.dimension))
+    }
+
+    fn is_id(&self, element: &Matrix<S>) -> bool {
+        element.dimension() == self.dimension && element.is_id()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        element::matrix::{
+            semiring::{IntegersMod, MaxPlusTruncated, NaturalTruncated},
+            Matrix,
+        },
+        semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+    };
+
+    use super::MatrixSemigroup;
+
+    #[test]
+    fn invalid_gens() {
+        let s = MatrixSemigroup::new(
+            IntegersMod::new(3),
+            &[
+                Matrix::id(IntegersMod::new(3), 2),
+                Matrix::id(IntegersMod::new(3), 3),
+            ],
+        );
+        assert!(s.is_err());
+        let s = MatrixSemigroup::new(IntegersMod::new(3), &[Matrix::id(IntegersMod::new(4), 2)]);
+        assert!(s.is_err());
+    }
+
+    #[test]
+    fn cyclic() {
+        // 2 generates {2, 4, 3, 1} in the integers mod 5, which includes the identity.
+        let sr = IntegersMod::new(5);
+        let s =
+            MatrixSemigroup::new(sr, &[Matrix::from_vec(sr, 1, vec![vec![2]]).unwrap()]).unwrap();
+        assert_eq!(FroidurePin::new(&s).build().size(), 4);
+        let sr = NaturalTruncated::new(3, 2);
+        let s =
+            MatrixSemigroup::new(sr, &[Matrix::from_vec(sr, 1, vec![vec![2]]).unwrap()]).unwrap();
+        // With threshold 3 and period 2, 2 generates {2, 4} as 8 = 4, so with the identity we have three elements.
+        assert_eq!(FroidurePin::new(&s).build().size(), 3);
+    }
+
+    #[test]
+    fn tropical_max_plus() {
+        let sr = MaxPlusTruncated::new(3);
+        let s = MatrixSemigroup::new(
+            sr,
+            &[
+                Matrix::from_vec(sr, 2, vec![vec![Some(1), None], vec![Some(0), Some(2)]]).unwrap(),
+                Matrix::from_vec(sr, 2, vec![vec![None, Some(0)], vec![Some(0), None]]).unwrap(),
+            ],
+        )
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        assert_eq!(res.size(), 23);
+        let (a, b) = (&res.generators()[0], &res.generators()[1]);
+        // In the max-plus semiring the product takes the maximum of the sums along each row and column.
+        let x = Matrix::from_vec(sr, 2, vec![vec![None, Some(1)], vec![Some(2), Some(0)]]).unwrap();
+        assert_eq!(a.multiply(b).unwrap(), x);
+        assert!(res.contains(&x));
+        // The entries are truncated at the threshold.
+        let x = Matrix::from_vec(sr, 2, vec![vec![Some(3), None], vec![Some(3), Some(3)]]).unwrap();
+        assert_eq!(a.multiply(a).unwrap().multiply(a).unwrap(), x);
+        assert!(res.contains(&x));
+        // The result is closed under multiplication.
+        for x in res.iter() {
+            for y in res.iter() {
+                assert!(res.contains(&x.multiply(y).unwrap()));
+            }
+        }
+    }
+}
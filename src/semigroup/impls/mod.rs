@@ -1 +1,6 @@
+pub mod bipartition;
+pub mod boolean_mat;
+pub mod matrix;
+pub mod partial_perm;
+pub mod permutation;
 pub mod transformation;
@@ -0,0 +1,102 @@
+use std::fmt::Display;
+
+use crate::element::partial_perm::{err::PartialPermError, PartialPerm};
+
+use super::super::Semigroup;
+
+/// Struct that represents a semigroup of partial permutations, such as the symmetric inverse monoid.
+pub struct PartialPermSemigroup {
+    degree: usize,
+    generators: Vec<PartialPerm>,
+}
+
+impl PartialPermSemigroup {
+    /// Create a new PartialPermSemigroup from a list of generators.
+    /// The new generators must have the same degree, otherwise an error is returned.
+    pub fn new(gens: &[PartialPerm]) -> Result<Self, PartialPermError> {
+        // Take degree of first element as degree of the semigroup. Need to handle trivial case.
+        let degree = gens.first().map(|f| f.degree()).unwrap_or(0);
+        // Must have same degree for all values
+        if let Some(f) = gens.iter().skip(1).find(|f| f.degree() != degree) {
+            Err(PartialPermError::MismatchingDegree {
+                degree1: degree,
+                degree2: f.degree(),
+            })
+        } else {
+            Ok(PartialPermSemigroup {
+                degree,
+                generators: gens.to_vec(),
+            })
+        }
+    }
+
+    /// Return the degree of the partial permutations in this Semigroup
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+}
+
+impl Display for PartialPermSemigroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<")?;
+        let mut sep = "";
+        for gen in self.generators() {
+            write!(f, "{}{}", sep, gen)?;
+            sep = ", "
+        }
+        write!(f, ">")
+    }
+}
+
+impl Semigroup<PartialPerm> for PartialPermSemigroup {
+    fn generators(&self) -> &[PartialPerm] {
+        &self.generators[..]
+    }
+
+    fn id(&self) -> Option<PartialPerm> {
+        Some(PartialPerm::id(self.degree))
+    }
+
+    fn is_id(&self, element: &PartialPerm) -> bool {
+        // Is the identity if it fixes all points.
+        (0..self.degree).all(|x| element.apply(x).is_ok_and(|y| y == Some(x)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        element::partial_perm::PartialPerm,
+        semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+    };
+
+    use super::PartialPermSemigroup;
+
+    #[test]
+    fn trivial_semigroup() {
+        assert!(PartialPermSemigroup::new(&[]).is_ok());
+    }
+
+    #[test]
+    fn invalid_gens() {
+        let f = PartialPerm::from_vec(2, vec![Some(1), None]).unwrap();
+        let g = PartialPerm::from_vec(3, vec![Some(1), None, Some(0)]).unwrap();
+        let s = PartialPermSemigroup::new(&[f, g]);
+        assert!(s.is_err());
+    }
+
+    #[test]
+    fn symmetric_inverse_monoid_4() {
+        let s = PartialPermSemigroup::new(&[
+            PartialPerm::from_vec(4, vec![Some(1), Some(0), Some(2), Some(3)]).unwrap(),
+            PartialPerm::from_vec(4, vec![Some(1), Some(2), Some(3), Some(0)]).unwrap(),
+            PartialPerm::from_vec(4, vec![None, Some(1), Some(2), Some(3)]).unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(s.to_string(), "<(0 1)(2)(3), (0 1 2 3), (1)(2)(3)>");
+        let res = FroidurePin::new(&s).build();
+        // Sum of binomial(4, k)^2 * k!
+        assert_eq!(res.size(), 209);
+        assert!(res.contains(&PartialPerm::empty(4)));
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt::Display;
+
+use crate::element::permutation::{err::PermutationError, Permutation};
+
+use super::super::{Group, Semigroup};
+
+/// Struct that represents a permutation group
+pub struct PermutationGroup {
+    degree: usize,
+    generators: Vec<Permutation>,
+}
+
+impl PermutationGroup {
+    /// Create a new PermutationGroup from a list of generators.
+    /// The new generators must have the same degree, otherwise an error is returned.
+    pub fn new(gens: &[Permutation]) -> Result<Self, PermutationError> {
+        // Take degree of first element as degree of the group. Need to handle trivial case.
+        let degree = gens.first().map(|f| f.degree()).unwrap_or(0);
+        // Must have same degree for all values
+        if let Some(f) = gens.iter().skip(1).find(|f| f.degree() != degree) {
+            Err(PermutationError::MismatchingDegree {
+                degree1: degree,
+                degree2: f.degree(),
+            })
+        } else {
+            Ok(PermutationGroup {
+                degree,
+                generators: gens.to_vec(),
+            })
+        }
+    }
+
+    /// Return the degree of the permutations in this group
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+}
+
+impl Display for PermutationGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<")?;
+        let mut sep = "";
+        for gen in self.generators() {
+            write!(f, "{}{}", sep, gen)?;
+            sep = ", "
+        }
+        write!(f, ">")
+    }
+}
+
+impl Semigroup<Permutation> for PermutationGroup {
+    fn generators(&self) -> &[Permutation] {
+        &self.generators[..]
+    }
+
+    fn id(&self) -> Option<Permutation> {
+        Some(self.identity())
+    }
+
+    fn is_id(&self, element: &Permutation) -> bool {
+        element.degree() == self.degree && element.is_id()
+    }
+}
+
+impl Group<Permutation> for PermutationGroup {
+    fn identity(&self) -> Permutation {
+        Permutation::id(self.degree)
+    }
+
+    fn inverse(&self, element: &Permutation) -> Permutation {
+        element.inverse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        element::permutation::Permutation,
+        semigroup::{
+            algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+            Group,
+        },
+    };
+
+    use super::PermutationGroup;
+
+    #[test]
+    fn trivial_group() {
+        assert!(PermutationGroup::new(&[]).is_ok());
+    }
+
+    #[test]
+    fn invalid_gens() {
+        let s = PermutationGroup::new(&[Permutation::id(2), Permutation::id(3)]);
+        assert!(s.is_err());
+    }
+
+    #[test]
+    fn symmetric_group_5() {
+        let g = PermutationGroup::new(&[
+            Permutation::from_cycle_notation(5, "(0 1)").unwrap(),
+            Permutation::from_cycle_notation(5, "(0 1 2 3 4)").unwrap(),
+        ])
+        .unwrap();
+        assert_eq!(g.to_string(), "<(0 1), (0 1 2 3 4)>");
+        let res = FroidurePin::new(&g).build();
+        assert_eq!(res.size(), 120);
+        // Inverses of the generators are in the group
+        for (gen, inv) in g.generators.iter().zip(g.inverse_generators()) {
+            assert!(res.contains(&inv));
+            assert_eq!(gen.multiply(&inv).unwrap(), g.identity());
+        }
+    }
+}
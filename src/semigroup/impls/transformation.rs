@@ -1,10 +1,15 @@
 use std::fmt::Display;
 
-use crate::element::transformation::{err::TransformationError, Transformation};
+use crate::element::transformation::{
+    err::TransformationError,
+    format::{invalid, parse_gap_transformation, parse_list, strip_whitespace, write_list},
+    Transformation,
+};
 
 use super::super::Semigroup;
 
 /// Struct that represents a transformation semigroup
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TransformationSemigroup {
     degree: usize,
     generators: Vec<Transformation>,
@@ -15,7 +20,7 @@ impl TransformationSemigroup {
     /// The new generators must have the same degree, otherwise an error is returned.
     pub fn new(gens: &[Transformation]) -> Result<Self, TransformationError> {
         // Take degree of first element as degree of Transformation Semigroup. Need to handle trivial case.
-        let degree = gens.get(0).map(|f| f.degree()).unwrap_or(0);
+        let degree = gens.first().map(|f| f.degree()).unwrap_or(0);
         // Must have same degree for all values
         if let Some(f) = gens.iter().skip(1).find(|f| f.degree() != degree) {
             Err(TransformationError::MismatchingDegree {
@@ -30,12 +35,161 @@ impl TransformationSemigroup {
         }
     }
 
+    /// Create the semigroup generated by the idempotents among the given transformations,
+    /// such as the elements of an enumerated semigroup.
+    /// As with every TransformationSemigroup, the result contains the identity.
+    /// The transformations must have the same degree, otherwise an error is returned.
+    /// ```
+    /// use yatyat::element::transformation::Transformation;
+    /// use yatyat::semigroup::algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder};
+    /// use yatyat::semigroup::impls::transformation::TransformationSemigroup;
+    ///
+    /// let s = TransformationSemigroup::new(&[
+    ///     Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+    ///     Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+    ///     Transformation::from_vec(3, vec![0, 0, 2]).unwrap(),
+    /// ])
+    /// .unwrap();
+    /// let res = FroidurePin::new(&s).build();
+    /// let elements: Vec<Transformation> = res.iter().cloned().collect();
+    /// let e = TransformationSemigroup::idempotent_generated(&elements).unwrap();
+    /// // The singular part of the full transformation monoid is idempotent generated.
+    /// assert_eq!(FroidurePin::new(&e).build().size(), 27 - 6 + 1);
+    /// ```
+    pub fn idempotent_generated(elements: &[Transformation]) -> Result<Self, TransformationError> {
+        let mut idempotents = Vec::new();
+        for f in elements.iter() {
+            if f.multiply(f)? == *f {
+                idempotents.push(f.clone());
+            }
+        }
+        Self::new(&idempotents)
+    }
+
+    /// Parse a semigroup written in GAP syntax, such as `Semigroup([Transformation([2, 1]), Transformation([1, 1])])`.
+    /// `Monoid` may be used in place of `Semigroup`, and the square brackets around the generators are optional.
+    /// As in GAP, transformations of smaller degree are extended to the largest degree by fixing the extra points.
+    /// ```
+    /// use yatyat::semigroup::impls::transformation::TransformationSemigroup;
+    ///
+    /// let s = TransformationSemigroup::from_gap("Monoid(Transformation([2, 1]), Transformation([1, 1, 1]))").unwrap();
+    /// assert_eq!(s.degree(), 3);
+    /// assert_eq!(s.to_gap(), "Monoid([Transformation([2, 1, 3]), Transformation([1, 1, 1])])");
+    /// ```
+    pub fn from_gap(input: &str) -> Result<Self, TransformationError> {
+        let stripped = strip_whitespace(input);
+        let inner = stripped
+            .strip_prefix("Semigroup(")
+            .or_else(|| stripped.strip_prefix("Monoid("))
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| invalid(input))?;
+        let mut rest = inner
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .unwrap_or(inner);
+        let mut images = Vec::new();
+        while !rest.is_empty() {
+            let (next, remaining) = parse_gap_transformation(rest).ok_or_else(|| invalid(input))?;
+            images.push(next);
+            rest = match remaining.strip_prefix(',') {
+                Some(remaining) if !remaining.is_empty() => remaining,
+                None if remaining.is_empty() => remaining,
+                _ => return Err(invalid(input)),
+            };
+        }
+        // Extend each transformation to the largest degree by fixing the extra points.
+        let degree = images.iter().map(|f| f.len()).max().unwrap_or(0);
+        let gens = images
+            .into_iter()
+            .map(|mut f| {
+                f.extend(f.len() + 1..=degree);
+                Transformation::from_gap_images(f)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::new(&gens)
+    }
+
+    /// Write the semigroup in GAP syntax. As a TransformationSemigroup contains the identity, this is written as a monoid.
+    pub fn to_gap(&self) -> String {
+        let gens: Vec<String> = self.generators.iter().map(|f| f.to_gap()).collect();
+        format!("Monoid([{}])", gens.join(", "))
+    }
+
+    /// Parse a semigroup written as a libsemigroups style list of generators, such as `{{1, 0, 2}, {0, 0, 2}}`.
+    /// Each list may be delimited by braces or square brackets, and the generators must all have the same degree.
+    /// ```
+    /// use yatyat::semigroup::impls::transformation::TransformationSemigroup;
+    ///
+    /// let s = TransformationSemigroup::from_libsemigroups("[[1, 0, 2], [0, 0, 2]]").unwrap();
+    /// assert_eq!(s.to_libsemigroups(), "{{1, 0, 2}, {0, 0, 2}}");
+    /// assert!(TransformationSemigroup::from_libsemigroups("{{1, 0}, {0, 0, 2}}").is_err());
+    /// ```
+    pub fn from_libsemigroups(input: &str) -> Result<Self, TransformationError> {
+        let stripped = strip_whitespace(input);
+        let inner = stripped
+            .strip_prefix('{')
+            .and_then(|rest| rest.strip_suffix('}'))
+            .or_else(|| {
+                stripped
+                    .strip_prefix('[')
+                    .and_then(|rest| rest.strip_suffix(']'))
+            })
+            .ok_or_else(|| invalid(input))?;
+        let mut rest = inner;
+        let mut gens = Vec::new();
+        while !rest.is_empty() {
+            let (images, remaining) = parse_list(rest).ok_or_else(|| invalid(input))?;
+            gens.push(Transformation::from_vec(images.len(), images)?);
+            rest = match remaining.strip_prefix(',') {
+                Some(remaining) if !remaining.is_empty() => remaining,
+                None if remaining.is_empty() => remaining,
+                _ => return Err(invalid(input)),
+            };
+        }
+        Self::new(&gens)
+    }
+
+    /// Write the semigroup as a libsemigroups style list of generators.
+    pub fn to_libsemigroups(&self) -> String {
+        let gens: Vec<String> = self
+            .generators
+            .iter()
+            .map(|f| write_list(f.as_vec(), '{', '}'))
+            .collect();
+        format!("{{{}}}", gens.join(", "))
+    }
+
     /// Return the degree of the transformations in this Semigroup
     pub fn degree(&self) -> usize {
         self.degree
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TransformationSemigroup {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Fields {
+            degree: usize,
+            generators: Vec<Transformation>,
+        }
+        let Fields { degree, generators } = Fields::deserialize(deserializer)?;
+        // Check the generators have the given degree, which matters when there are none.
+        if let Some(f) = generators.iter().find(|f| f.degree() != degree) {
+            return Err(serde::de::Error::custom(
+                TransformationError::MismatchingDegree {
+                    degree1: degree,
+                    degree2: f.degree(),
+                },
+            ));
+        }
+        Ok(Self { degree, generators })
+    }
+}
+
 impl Display for TransformationSemigroup {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "<")?;
@@ -59,7 +213,7 @@ impl Semigroup<Transformation> for TransformationSemigroup {
 
     fn is_id(&self, element: &Transformation) -> bool {
         // Is the identity if it fixes all points.
-        (0..self.degree).all(|x| element.apply(x).map_or(false, |y| y == x))
+        (0..self.degree).all(|x| element.apply(x).is_ok_and(|y| y == x))
     }
 }
 
@@ -90,4 +244,40 @@ mod test {
         let s = TransformationSemigroup::new(&[f, g]);
         assert!(s.is_err());
     }
+
+    #[test]
+    fn formats() {
+        let f = Transformation::from_vec(4, vec![2, 2, 3, 1]).unwrap();
+        let g = Transformation::from_vec(4, vec![1, 0, 2, 3]).unwrap();
+        let s = TransformationSemigroup::new(&[f, g]).unwrap();
+        let gap = TransformationSemigroup::from_gap(&s.to_gap()).unwrap();
+        assert_eq!(gap.generators, s.generators);
+        let lists = TransformationSemigroup::from_libsemigroups(&s.to_libsemigroups()).unwrap();
+        assert_eq!(lists.generators, s.generators);
+        let empty = TransformationSemigroup::from_gap("Semigroup( [ ] )").unwrap();
+        assert_eq!(empty.degree(), 0);
+        assert_eq!(empty.to_libsemigroups(), "{}");
+        assert!(TransformationSemigroup::from_gap("Group([Transformation([1])])").is_err());
+        assert!(TransformationSemigroup::from_gap("Monoid([Transformation([1]),])").is_err());
+        assert!(TransformationSemigroup::from_gap(
+            "Monoid(Transformation([1])Transformation([1]))"
+        )
+        .is_err());
+        assert!(TransformationSemigroup::from_libsemigroups("{{0}, {0}").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let f = Transformation::from_vec(3, vec![2, 2, 1]).unwrap();
+        let s = TransformationSemigroup::new(&[f]).unwrap();
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, r#"{"degree":3,"generators":[[2,2,1]]}"#);
+        let t: TransformationSemigroup = serde_json::from_str(&json).unwrap();
+        assert_eq!(t.generators, s.generators);
+        assert!(serde_json::from_str::<TransformationSemigroup>(
+            r#"{"degree":2,"generators":[[2,2,1]]}"#
+        )
+        .is_err());
+    }
 }
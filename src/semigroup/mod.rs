@@ -4,6 +4,7 @@ use crate::element::SemigroupElement;
 
 pub mod algs;
 pub mod impls;
+pub mod presentation;
 pub mod word;
 
 /// Trait to represent a semigroup, which is given as a list of generators.
@@ -24,3 +25,20 @@ where
     /// Return the generators of this Semigroup.
     fn generators(&self) -> &[U];
 }
+
+/// Trait to represent a group, which is a monoid where every element has an inverse.
+///
+/// This allows algorithms to specialise on groups, rather than treating them as general semigroups.
+pub trait Group<U>: Semigroup<U>
+where
+    U: SemigroupElement,
+{
+    /// Return the identity of the group.
+    fn identity(&self) -> U;
+    /// Return the inverse of a given element.
+    fn inverse(&self, element: &U) -> U;
+    /// Return the inverses of the generators, in the same order as the generators.
+    fn inverse_generators(&self) -> Vec<U> {
+        self.generators().iter().map(|x| self.inverse(x)).collect()
+    }
+}
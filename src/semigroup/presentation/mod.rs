@@ -0,0 +1,363 @@
+use std::{fmt::Display, hash::Hash};
+
+use itertools::Itertools;
+use snafu::Snafu;
+
+use crate::{semigroup::word::Word, DetHashSet};
+
+/// A finite presentation of a semigroup or monoid, given by an alphabet and relations between words over it.
+/// If the presentation contains the empty word, then it is a monoid presentation and the empty word represents the identity.
+#[derive(Debug, Clone)]
+pub struct Presentation<A>
+where
+    A: Ord + Clone,
+{
+    alphabet: Vec<A>,
+    relations: Vec<(Word<A>, Word<A>)>,
+    contains_empty_word: bool,
+    // The short-lex normal forms of the elements, if they are already known.
+    normal_forms: Option<Vec<Word<A>>>,
+}
+
+impl<A> Presentation<A>
+where
+    A: Ord + Clone + Hash + Display,
+{
+    /// Create a presentation from an alphabet and relations.
+    /// An error is returned if a symbol is repeated in the alphabet, a relation uses a symbol not in the alphabet,
+    /// or a relation uses the empty word when the presentation does not contain it.
+    /// ```
+    /// use yatyat::semigroup::{presentation::Presentation, word::Word};
+    ///
+    /// let aaa: Word<char> = "aaa".chars().collect();
+    /// assert!(Presentation::new(vec!['a'], vec![(aaa.clone(), Word::empty_word())], true).is_ok());
+    /// assert!(Presentation::new(vec!['a'], vec![(aaa, Word::empty_word())], false).is_err());
+    /// ```
+    pub fn new(
+        alphabet: Vec<A>,
+        relations: Vec<(Word<A>, Word<A>)>,
+        contains_empty_word: bool,
+    ) -> Result<Self, PresentationError<A>> {
+        let mut symbols = DetHashSet::default();
+        for symbol in alphabet.iter() {
+            if !symbols.insert(symbol) {
+                return Err(PresentationError::DuplicateSymbol {
+                    symbol: symbol.clone(),
+                });
+            }
+        }
+        for (lhs, rhs) in relations.iter() {
+            if !contains_empty_word && (lhs.is_empty_word() || rhs.is_empty_word()) {
+                return Err(PresentationError::EmptyWord {});
+            }
+            if let Some(symbol) = lhs
+                .as_vec()
+                .iter()
+                .chain(rhs.as_vec())
+                .find(|a| !symbols.contains(a))
+            {
+                return Err(PresentationError::MissingSymbol {
+                    symbol: symbol.clone(),
+                });
+            }
+        }
+        Ok(Self::from_vec_unchecked(
+            alphabet,
+            relations,
+            contains_empty_word,
+        ))
+    }
+
+    /// Create a presentation without performing the validation.
+    pub(crate) fn from_vec_unchecked(
+        alphabet: Vec<A>,
+        relations: Vec<(Word<A>, Word<A>)>,
+        contains_empty_word: bool,
+    ) -> Self {
+        Self {
+            alphabet,
+            relations,
+            contains_empty_word,
+            normal_forms: None,
+        }
+    }
+
+    /// Record the normal forms of the elements, which must be the short-lex normal forms of the semigroup presented.
+    pub(crate) fn with_normal_forms(mut self, normal_forms: Vec<Word<A>>) -> Self {
+        self.normal_forms = Some(normal_forms);
+        self
+    }
+
+    /// Return the symbols of the alphabet.
+    pub fn alphabet(&self) -> &[A] {
+        &self.alphabet[..]
+    }
+
+    /// Return the defining relations.
+    pub fn relations(&self) -> &[(Word<A>, Word<A>)] {
+        &self.relations[..]
+    }
+
+    /// Return the defining relations to be changed in a way that keeps the semigroup presented the same,
+    /// so any known normal forms are still correct.
+    pub(crate) fn relations_mut(&mut self) -> &mut Vec<(Word<A>, Word<A>)> {
+        &mut self.relations
+    }
+
+    /// Return if this is a monoid presentation, so the empty word represents the identity.
+    pub fn contains_empty_word(&self) -> bool {
+        self.contains_empty_word
+    }
+
+    /// Remove relations of the form u = u, and any relation that appears more than once, in either orientation.
+    pub fn remove_trivial_relations(&mut self) {
+        let mut seen = DetHashSet::default();
+        self.relations.retain(|(lhs, rhs)| {
+            let key = if lhs < rhs {
+                (lhs.clone(), rhs.clone())
+            } else {
+                (rhs.clone(), lhs.clone())
+            };
+            lhs != rhs && seen.insert(key)
+        });
+    }
+
+    /// Remove relations that follow from the relations before them by rewriting.
+    ///
+    /// The relations are considered from first to last, and each relation kept is used as a rewriting rule from its
+    /// short-lex greater side to its smaller side. A relation is removed if both of its sides rewrite to the same word
+    /// using the rules kept so far, so every relation removed is a consequence of those kept and the presentation
+    /// still defines the same semigroup.
+    /// Only derivations that never make a word larger in short-lex order are found, so the result is not guaranteed
+    /// to be minimal or confluent.
+    /// A reduced confluent presentation, such as the rules from [`FroidurePinResult::rules`], is left unchanged.
+    ///
+    /// [`FroidurePinResult::rules`]: crate::semigroup::algs::froidure_pin::FroidurePinResult::rules
+    /// ```
+    /// use yatyat::semigroup::{presentation::Presentation, word::Word};
+    ///
+    /// let word = |s: &str| -> Word<char> { s.chars().collect() };
+    /// let mut p = Presentation::new(
+    ///     vec!['a', 'b'],
+    ///     vec![(word("ab"), word("ba")), (word("aa"), word("a")), (word("aab"), word("ba"))],
+    ///     false,
+    /// )
+    /// .unwrap();
+    /// p.remove_redundant_relations();
+    /// assert_eq!(p.relations().len(), 2);
+    /// ```
+    pub fn remove_redundant_relations(&mut self) {
+        self.remove_trivial_relations();
+        let mut rules: Vec<(Vec<A>, Vec<A>)> = Vec::new();
+        self.relations.retain(|(lhs, rhs)| {
+            let lhs = Self::rewrite(&rules, lhs.as_vec());
+            let rhs = Self::rewrite(&rules, rhs.as_vec());
+            if lhs == rhs {
+                return false;
+            }
+            // Orient the rule so that it makes words smaller in short-lex order.
+            if (lhs.len(), &lhs) > (rhs.len(), &rhs) {
+                rules.push((lhs, rhs));
+            } else {
+                rules.push((rhs, lhs));
+            }
+            true
+        });
+    }
+
+    /// Return the short-lex normal form of each element of the semigroup presented, in short-lex order,
+    /// if they are known, such as for a presentation from [`FroidurePinResult::presentation`].
+    ///
+    /// [`FroidurePinResult::presentation`]: crate::semigroup::algs::froidure_pin::FroidurePinResult::presentation
+    pub fn normal_forms(&self) -> Option<&[Word<A>]> {
+        self.normal_forms.as_deref()
+    }
+
+    /// Rewrite a word with the rules until no left hand side of a rule is a subword.
+    /// This terminates as each rule makes the word smaller in short-lex order.
+    fn rewrite(rules: &[(Vec<A>, Vec<A>)], word: &[A]) -> Vec<A> {
+        let mut word = word.to_vec();
+        'rewrite: loop {
+            for (from, to) in rules.iter() {
+                if let Some(pos) = word.windows(from.len()).position(|w| w == &from[..]) {
+                    word.splice(pos..pos + from.len(), to.iter().cloned());
+                    continue 'rewrite;
+                }
+            }
+            return word;
+        }
+    }
+}
+
+// The normal forms are determined by the rest of the presentation, so they are not compared.
+impl<A> PartialEq for Presentation<A>
+where
+    A: Ord + Clone,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.alphabet == other.alphabet
+            && self.relations == other.relations
+            && self.contains_empty_word == other.contains_empty_word
+    }
+}
+
+impl<A> Eq for Presentation<A> where A: Ord + Clone {}
+
+impl<A> Display for Presentation<A>
+where
+    A: Display + Ord + Clone,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fmt_word = |word: &Word<A>| {
+            if word.is_empty_word() {
+                "ε".to_string()
+            } else {
+                // Letters are separated so that symbols with more than one character can be read back.
+                word.as_vec().iter().join(" ")
+            }
+        };
+        write!(f, "<")?;
+        let mut sep = "";
+        for symbol in self.alphabet.iter() {
+            write!(f, "{}{}", sep, symbol)?;
+            sep = ", ";
+        }
+        write!(f, " |")?;
+        let mut sep = " ";
+        for (lhs, rhs) in self.relations.iter() {
+            write!(f, "{}{} = {}", sep, fmt_word(lhs), fmt_word(rhs))?;
+            sep = ", ";
+        }
+        write!(f, ">")
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum PresentationError<A>
+where
+    A: Display,
+{
+    #[snafu(display("Symbol not in alphabet: {}", symbol))]
+    MissingSymbol { symbol: A },
+    #[snafu(display("Symbol already in alphabet: {}", symbol))]
+    DuplicateSymbol { symbol: A },
+    #[snafu(display("Empty word used in a semigroup presentation"))]
+    EmptyWord {},
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Presentation;
+    use crate::{
+        element::transformation::Transformation,
+        semigroup::{
+            algs::froidure_pin::{froidure_pin_impl::FroidurePin, FroidurePinBuilder},
+            impls::transformation::TransformationSemigroup,
+            word::Word,
+        },
+    };
+
+    fn word(s: &str) -> Word<char> {
+        s.chars().collect()
+    }
+
+    #[test]
+    fn invalid_presentations() {
+        assert!(Presentation::new(vec!['a', 'a'], vec![], false).is_err());
+        assert!(Presentation::new(vec!['a'], vec![(word("ab"), word("a"))], false).is_err());
+        assert!(Presentation::new(vec!['a'], vec![(word("a"), word(""))], false).is_err());
+    }
+
+    #[test]
+    fn trivial_relations() {
+        let mut p = Presentation::new(
+            vec!['a', 'b'],
+            vec![
+                (word("ab"), word("ab")),
+                (word("ab"), word("ba")),
+                (word("ba"), word("ab")),
+            ],
+            false,
+        )
+        .unwrap();
+        p.remove_trivial_relations();
+        assert_eq!(p.relations(), &[(word("ab"), word("ba"))]);
+        assert_eq!(p.to_string(), "<a, b | a b = b a>");
+    }
+
+    #[test]
+    fn display() {
+        let word = |w: &[usize]| w.iter().copied().collect::<Word<usize>>();
+        let p = Presentation::new(
+            vec![1, 11],
+            vec![
+                (word(&[1, 11]), word(&[11, 1])),
+                (word(&[11, 11]), word(&[])),
+            ],
+            true,
+        )
+        .unwrap();
+        assert_eq!(p.to_string(), "<1, 11 | 1 11 = 11 1, 11 11 = ε>");
+    }
+
+    #[test]
+    fn redundant_relations() {
+        let mut p = Presentation::new(
+            vec!['a', 'b'],
+            vec![
+                (word("aa"), word("a")),
+                (word("ba"), word("ab")),
+                (word("aba"), word("ab")),
+                (word("bb"), word("b")),
+                (word("bab"), word("ab")),
+            ],
+            false,
+        )
+        .unwrap();
+        p.remove_redundant_relations();
+        // aba rewrites to aab and then ab, and bab rewrites to abb and then ab once bb = b is kept.
+        assert_eq!(p.to_string(), "<a, b | a a = a, b a = a b, b b = b>");
+    }
+
+    #[test]
+    fn froidure_pin_presentation() {
+        // Symmetric group on 3 points, generated by a transposition and a 3-cycle.
+        let s = TransformationSemigroup::new(&[
+            Transformation::from_vec(3, vec![1, 0, 2]).unwrap(),
+            Transformation::from_vec(3, vec![1, 2, 0]).unwrap(),
+        ])
+        .unwrap();
+        let res = FroidurePin::new(&s).build();
+        let mut p = res.presentation();
+        assert!(p.contains_empty_word());
+        assert_eq!(p.alphabet(), &[0, 1]);
+        assert_eq!(p.relations(), res.rules());
+        assert_eq!(p.normal_forms(), Some(&res.normal_forms()[..]));
+        // The rules are reduced, so none of them can be removed by rewriting with the others.
+        p.remove_redundant_relations();
+        assert_eq!(p.relations(), res.rules());
+        // The normal forms are not compared, as they are determined by the rest of the presentation.
+        let q = Presentation::new(p.alphabet().to_vec(), p.relations().to_vec(), true).unwrap();
+        assert_eq!(q.normal_forms(), None);
+        assert_eq!(p, q);
+        let evaluate = |word: &Word<usize>| {
+            word.as_vec().iter().fold(Transformation::id(3), |acc, &a| {
+                acc.multiply(&res.generators()[a]).unwrap()
+            })
+        };
+        // Each left hand side rewrites to the normal form of the element.
+        for (lhs, rhs) in res.rules() {
+            let position = res.position(&evaluate(lhs));
+            assert_eq!(position, res.position(&evaluate(rhs)));
+            assert_eq!(res.normal_form(position.unwrap()).as_ref(), Some(rhs));
+        }
+        // The normal forms should be in short-lex order and evaluate to the elements.
+        let normal_forms = res.normal_forms();
+        assert!(normal_forms.windows(2).all(|w| w[0] < w[1]));
+        for (nf, element) in normal_forms.iter().zip(res.iter()) {
+            assert_eq!(&evaluate(nf), element);
+        }
+        assert_eq!(res.normal_form(0), Some(Word::empty_word()));
+        assert_eq!(res.normal_form(res.size()), None);
+    }
+}
@@ -123,6 +123,11 @@ where
 /// Struct that represents a word from an alphabet.
 /// This should be used in the context of an Alphabet, to provide sanity checking.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Word<A>
 where
     A: Clone,
@@ -145,6 +150,11 @@ where
         self.word.is_empty()
     }
 
+    /// Return the symbols of the word in order.
+    pub fn as_vec(&self) -> &[A] {
+        &self.word[..]
+    }
+
     /// Append to a word, giving a new word
     pub fn append(&self, a: &A) -> Self {
         self.word
@@ -162,6 +172,7 @@ where
     }
 
     /// Return the length of the word.
+    #[allow(clippy::len_without_is_empty)]
     pub fn len(&self) -> usize {
         self.word.len()
     }
@@ -196,13 +207,7 @@ where
     A: Ord + Clone,
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        // If the lenghts are different, then compare by length
-        if self.len() != other.len() {
-            self.len().partial_cmp(&other.len())
-        // Otherwise compare lexicographically.
-        } else {
-            self.word.partial_cmp(&other.word)
-        }
+        Some(self.cmp(other))
     }
 }
 
@@ -211,6 +216,8 @@ where
     A: Ord + Clone,
 {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // If the lenghts are different, then compare by length
+        // Otherwise compare lexicographically.
         if self.len() != other.len() {
             self.len().cmp(&other.len())
         } else {
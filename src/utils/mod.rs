@@ -1 +1,3 @@
+pub mod scc;
+pub mod union_find;
 pub mod vec2;
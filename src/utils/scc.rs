@@ -0,0 +1,110 @@
+/// Compute the strongly connected components of the graph on the nodes 0..n,
+/// where each node has `n_edges` edges and `target(i, e)` is the target of edge e out of node i, or None if it is missing.
+///
+/// Returns the index of the component of each node, where components are numbered by their smallest node.
+pub fn strongly_connected_components<F>(n: usize, n_edges: usize, target: F) -> Vec<usize>
+where
+    F: Fn(usize, usize) -> Option<usize>,
+{
+    // Return the target of the next edge out of the node and move past it.
+    let next_target = |node: usize, edge: &mut usize| {
+        while *edge < n_edges {
+            *edge += 1;
+            if let Some(w) = target(node, *edge - 1) {
+                return Some(w);
+            }
+        }
+        None
+    };
+    // Iterative version of Tarjan's algorithm, so large graphs do not overflow the stack.
+    let mut index = vec![None; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut component = vec![usize::MAX; n];
+    let mut next_index = 0;
+    let mut n_components = 0;
+    for root in 0..n {
+        if index[root].is_some() {
+            continue;
+        }
+        // Each frame stores a node and the next of its edges to follow.
+        let mut calls = vec![(root, 0)];
+        index[root] = Some(next_index);
+        low[root] = next_index;
+        next_index += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        while let Some((v, edge)) = calls.last_mut() {
+            let v = *v;
+            if let Some(w) = next_target(v, edge) {
+                match index[w] {
+                    None => {
+                        index[w] = Some(next_index);
+                        low[w] = next_index;
+                        next_index += 1;
+                        stack.push(w);
+                        on_stack[w] = true;
+                        calls.push((w, 0));
+                    }
+                    Some(w_index) if on_stack[w] => low[v] = low[v].min(w_index),
+                    Some(_) => {}
+                }
+            } else {
+                calls.pop();
+                if let Some(&(u, _)) = calls.last() {
+                    low[u] = low[u].min(low[v]);
+                }
+                if Some(low[v]) == index[v] {
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component[w] = n_components;
+                        if w == v {
+                            break;
+                        }
+                    }
+                    n_components += 1;
+                }
+            }
+        }
+    }
+    // Renumber the components in order of their smallest node.
+    let mut renumber = vec![None; n_components];
+    let mut next = 0;
+    for c in component.iter_mut() {
+        *c = *renumber[*c].get_or_insert_with(|| {
+            next += 1;
+            next - 1
+        });
+    }
+    component
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strongly_connected_components;
+    use crate::utils::vec2::Vec2;
+
+    fn graph_components(graphs: &[&Vec2<Option<usize>>]) -> Vec<usize> {
+        let n_cols = graphs[0].n_cols();
+        strongly_connected_components(graphs[0].n_rows(), graphs.len() * n_cols, |x, e| {
+            graphs[e / n_cols][(x, e % n_cols)]
+        })
+    }
+
+    #[test]
+    fn components() {
+        // 0 -> 1 -> 2 -> 1, 3 -> 0, 4 alone
+        let mut graph = Vec2::new(5, 1);
+        graph[(0, 0)] = Some(1);
+        graph[(1, 0)] = Some(2);
+        graph[(2, 0)] = Some(1);
+        graph[(3, 0)] = Some(0);
+        assert_eq!(graph_components(&[&graph]), vec![0, 1, 1, 2, 3]);
+        // Adding the reverse edges from a second graph merges 0..3 together.
+        let mut reverse = Vec2::new(5, 1);
+        reverse[(1, 0)] = Some(0);
+        reverse[(0, 0)] = Some(3);
+        assert_eq!(graph_components(&[&graph, &reverse]), vec![0, 0, 0, 0, 1]);
+    }
+}
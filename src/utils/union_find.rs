@@ -0,0 +1,110 @@
+/// Disjoint set forest on the points 0..n-1, using path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl UnionFind {
+    /// Create a new forest where every point is in its own set.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    /// Add a new point in its own set, returning the point.
+    pub fn add(&mut self) -> usize {
+        let x = self.parent.len();
+        self.parent.push(x);
+        self.size.push(1);
+        x
+    }
+
+    /// Return the number of points.
+    pub fn n_points(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Return if x is the representative of its set.
+    pub fn is_root(&self, x: usize) -> bool {
+        self.parent[x] == x
+    }
+
+    /// Find the representative of the set containing x.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // Compress the path so later lookups are faster
+        let mut cur = x;
+        while self.parent[cur] != root {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+        root
+    }
+
+    /// Merge the sets containing x and y, returning true if they were previously distinct.
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (mut x, mut y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        // Attach the smaller tree to the larger
+        if self.size[x] < self.size[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        true
+    }
+
+    /// Merge the sets containing x and y, keeping the smaller of their representatives as the representative.
+    /// Return true if they were previously distinct.
+    pub fn union_keep_smaller(&mut self, x: usize, y: usize) -> bool {
+        let (x, y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        let (keep, remove) = (x.min(y), x.max(y));
+        self.parent[remove] = keep;
+        self.size[keep] += self.size[remove];
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UnionFind;
+
+    #[test]
+    fn union_find() {
+        let mut uf = UnionFind::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+        assert_eq!(uf.find(0), uf.find(3));
+        assert_ne!(uf.find(0), uf.find(4));
+        assert_ne!(uf.find(4), uf.find(5));
+    }
+
+    #[test]
+    fn keep_smaller() {
+        let mut uf = UnionFind::new(3);
+        assert_eq!(uf.add(), 3);
+        assert_eq!(uf.n_points(), 4);
+        assert!(uf.union(1, 2));
+        assert!(uf.union_keep_smaller(3, 2));
+        assert_eq!(uf.find(3), 1);
+        assert!(uf.union_keep_smaller(3, 0));
+        assert!(!uf.union_keep_smaller(2, 0));
+        assert_eq!(uf.find(2), 0);
+        assert!(uf.is_root(0));
+        assert!(!uf.is_root(1));
+    }
+}
@@ -7,6 +7,7 @@ use std::vec;
 /// This is backed by a single vector, which is more efficient than nested vectors.
 /// We index by row and then column
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Vec2<T> {
     n_rows: usize,
     n_cols: usize,
@@ -36,7 +37,7 @@ where
 
     pub fn get_row(&self, row: usize) -> &[T] {
         let offset = row * self.n_cols;
-        &self.vec[(offset..offset + self.n_cols)]
+        &self.vec[offset..offset + self.n_cols]
     }
 
     /// Add a new row to the array, filling with the default value
@@ -52,6 +53,7 @@ where
     }
 
     /// Add a new column to the array, filling with the default value
+    #[allow(dead_code)]
     pub fn add_col(&mut self) {
         // Reserve space for one new value in each row
         self.vec.reserve(self.n_rows);
@@ -64,11 +66,16 @@ where
         self.n_cols += 1;
     }
 
+    /// Add new columns to the array, filling with the default value
     pub fn add_cols(&mut self, nr: usize) {
-        // Could be made more efficient
-        for _ in 0..nr {
-            self.add_col();
+        // Copy each row into a new vector followed by the new values, rather than inserting into each row in turn.
+        let mut vec = Vec::with_capacity(self.n_rows * (self.n_cols + nr));
+        for row in 0..self.n_rows {
+            vec.extend_from_slice(self.get_row(row));
+            vec.extend(repeat(T::default()).take(nr));
         }
+        self.vec = vec;
+        self.n_cols += nr;
     }
 }
 
@@ -86,6 +93,43 @@ impl<T> IndexMut<(usize, usize)> for Vec2<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Vec2<T>
+where
+    T: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(serde::Deserialize)]
+        struct Fields<T> {
+            n_rows: usize,
+            n_cols: usize,
+            vec: Vec<T>,
+        }
+        let Fields {
+            n_rows,
+            n_cols,
+            vec,
+        } = Fields::deserialize(deserializer)?;
+        // Check there is exactly one value for each entry, as indexing assumes this.
+        if n_rows.checked_mul(n_cols) != Some(vec.len()) {
+            return Err(serde::de::Error::custom(format_args!(
+                "{} values for {} rows and {} columns",
+                vec.len(),
+                n_rows,
+                n_cols
+            )));
+        }
+        Ok(Self {
+            n_rows,
+            n_cols,
+            vec,
+        })
+    }
+}
+
 impl<T> std::fmt::Display for Vec2<T>
 where
     T: Display,
@@ -100,13 +144,14 @@ where
                 write!(f, "{}{}", sep, self[(i, j)])?;
                 sep = ",";
             }
-            write!(f, "]\n")?;
+            writeln!(f, "]")?;
         }
         write!(f, "]")
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::op_ref)]
 mod tests {
     use super::Vec2;
 
@@ -163,6 +208,31 @@ mod tests {
         assert!(vec.get_row(2) == &[1, 0, 0, 6]);
     }
 
+    #[test]
+    fn add_cols() {
+        let mut vec = Vec2::<usize>::new(2, 2);
+        vec[(0, 1)] = 3;
+        vec[(1, 0)] = 4;
+        vec.add_cols(2);
+        assert!(vec.n_cols() == 4);
+        assert!(vec.get_row(0) == [0, 3, 0, 0]);
+        assert!(vec.get_row(1) == [4, 0, 0, 0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde() {
+        let mut vec = Vec2::<usize>::new(2, 3);
+        vec[(1, 2)] = 4;
+        let json = serde_json::to_string(&vec).unwrap();
+        assert_eq!(json, r#"{"n_rows":2,"n_cols":3,"vec":[0,0,0,0,0,4]}"#);
+        assert_eq!(serde_json::from_str::<Vec2<usize>>(&json).unwrap(), vec);
+        assert!(serde_json::from_str::<Vec2<usize>>(
+            r#"{"n_rows":2,"n_cols":3,"vec":[0,0,0,0,0]}"#
+        )
+        .is_err());
+    }
+
     #[test]
     fn get_row() {
         let mut vec = Vec2::<usize>::new(3, 3);